@@ -1,4 +1,6 @@
-pub mod discord_service;
-pub mod rss_summary;
+pub mod notification_metadata;
+pub mod prompt_template;
 pub mod rss_summary_service;
+pub mod summary_chunker;
+pub mod summary_validator;
 pub mod use_case;
@@ -0,0 +1,109 @@
+use chrono::DateTime;
+use rss::{Channel, Item};
+use std::collections::HashMap;
+
+use crate::application::summary_validator::normalize_link;
+use crate::domain::notification::NotificationMetadata;
+
+/// RSSチャンネルから通知の付加情報を作成する構造体
+pub struct NotificationMetadataBuilder<'a> {
+    channel: &'a Channel,
+    items_by_link: HashMap<String, &'a Item>,
+}
+
+impl<'a> NotificationMetadataBuilder<'a> {
+    /// 新しいビルダーを作成する
+    ///
+    /// # Arguments
+    /// * `channel` - 通知の元になったRSSチャンネル
+    pub fn new(channel: &'a Channel) -> Self {
+        let items_by_link = channel
+            .items()
+            .iter()
+            .filter_map(|item| item.link().map(|link| (normalize_link(link), item)))
+            .collect();
+
+        Self {
+            channel,
+            items_by_link,
+        }
+    }
+
+    /// カテゴリに含まれる記事の付加情報を作成する
+    ///
+    /// サムネイルは最初に画像を持つ記事のものを使用し、どの記事も持たない場合はフィードの画像を使用する
+    ///
+    /// # Arguments
+    /// * `links` - カテゴリに含まれる記事のリンク
+    pub fn build<'l>(&self, links: impl IntoIterator<Item = &'l str>) -> NotificationMetadata {
+        let items: Vec<&Item> = links
+            .into_iter()
+            .filter_map(|link| self.items_by_link.get(&normalize_link(link)).copied())
+            .collect();
+
+        let thumbnail_url = items
+            .iter()
+            .find_map(|item| item_image(item))
+            .or_else(|| self.channel.image().map(|image| image.url().to_string()));
+
+        let timestamp = items
+            .iter()
+            .filter_map(|item| item.pub_date())
+            .filter_map(|date| DateTime::parse_from_rfc2822(date).ok())
+            .max()
+            .map(|date| date.to_rfc3339());
+
+        NotificationMetadata {
+            source_name: non_empty(self.channel.title()),
+            source_url: non_empty(self.channel.link()),
+            thumbnail_url,
+            timestamp,
+        }
+    }
+}
+
+/// アイテムの画像のURLを取得する
+///
+/// media:thumbnail、画像の media:content、画像のenclosureの順に探す
+///
+/// # Arguments
+/// * `item` - RSSアイテム
+fn item_image(item: &Item) -> Option<String> {
+    let media = item.extensions().get("media");
+    let media_url = |name: &str, require_image: bool| {
+        media?
+            .get(name)?
+            .iter()
+            .filter(|extension| {
+                !require_image
+                    || extension
+                        .attrs()
+                        .get("medium")
+                        .map(|medium| medium == "image")
+                        .or_else(|| extension.attrs().get("type").map(|mime| mime.starts_with("image/")))
+                        .unwrap_or(false)
+            })
+            .find_map(|extension| extension.attrs().get("url").cloned())
+    };
+
+    media_url("thumbnail", false)
+        .or_else(|| media_url("content", true))
+        .or_else(|| {
+            item.enclosure()
+                .filter(|enclosure| enclosure.mime_type().starts_with("image/"))
+                .map(|enclosure| enclosure.url().to_string())
+        })
+}
+
+/// 空の文字列を `None` に変換する
+///
+/// # Arguments
+/// * `value` - 対象の文字列
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
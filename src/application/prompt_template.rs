@@ -0,0 +1,73 @@
+use crate::domain::rss_summary::RssSummaryError;
+
+/// プロンプトテンプレートを表す構造体
+///
+/// `{{name}}` 形式のプレースホルダーを変数の値で置き換える
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    content: String,
+}
+
+/// プロンプトテンプレートに埋め込む変数を表す構造体
+#[derive(Debug, Clone)]
+pub struct PromptVariables {
+    pub feed_title: String,
+    pub language: String,
+    pub date: String,
+    pub max_categories: usize,
+}
+
+impl PromptTemplate {
+    /// 新しいプロンプトテンプレートを作成する
+    ///
+    /// # Arguments
+    /// * `content` - テンプレートの内容
+    pub fn new(content: String) -> Self {
+        Self { content }
+    }
+
+    /// テンプレートに記事一覧のプレースホルダーが含まれているかどうかを確認する
+    pub fn has_items_placeholder(&self) -> bool {
+        self.content.contains("{{items_json}}")
+    }
+
+    /// 変数を埋め込んだプロンプトを作成する
+    ///
+    /// 埋め込んだ値に含まれるプレースホルダーは置き換えないよう、テンプレートを先頭から1度だけ走査する
+    ///
+    /// # Arguments
+    /// * `variables` - 埋め込む変数
+    /// * `items_json` - 記事一覧のJSON
+    pub fn render(&self, variables: &PromptVariables, items_json: &str) -> Result<String, RssSummaryError> {
+        let max_categories = variables.max_categories.to_string();
+        let mut rendered = String::with_capacity(self.content.len() + items_json.len());
+        let mut rest = self.content.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            let Some(length) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let name = &rest[start + 2..start + 2 + length];
+            let value = match name {
+                "feed_title" => variables.feed_title.as_str(),
+                "language" => variables.language.as_str(),
+                "date" => variables.date.as_str(),
+                "max_categories" => max_categories.as_str(),
+                "items_json" => items_json,
+                unknown => {
+                    return Err(RssSummaryError::Template(format!(
+                        "unknown placeholder {{{{{}}}}}",
+                        unknown
+                    )))
+                }
+            };
+
+            rendered.push_str(&rest[..start]);
+            rendered.push_str(value);
+            rest = &rest[start + 2 + length + 2..];
+        }
+        rendered.push_str(rest);
+
+        Ok(rendered)
+    }
+}
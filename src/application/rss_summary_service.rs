@@ -1,29 +1,65 @@
 use base64::{engine::general_purpose, Engine as _};
-use log::{error, warn};
+use chrono::Local;
+use log::{error, info, warn};
 use rss::Channel;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::env;
 use std::error::Error;
+use std::fs;
 
-use crate::domain::ai_service::AiServiceError;
+use crate::application::prompt_template::{PromptTemplate, PromptVariables};
+use crate::application::summary_chunker::{estimate_tokens, merge_summaries, split_into_chunks};
+use crate::domain::ai_service::{AiRequest, AiService};
+use crate::domain::model::feed::Feed;
 use crate::domain::model::rss_data::RssData;
+use crate::domain::repository::summary_cache::SummaryCache;
 use crate::domain::rss_summary::{RssSummaryError, RssSummaryService};
-use crate::domain::model::rss_summary::ArticlesResponse;
-use crate::infrastructure::gemini::{GeminiRequest, GeminiResponse};
-use crate::infrastructure::gemini::request::{Content, Part};
-use crate::infrastructure::http_client::HttpClient;
+use crate::domain::model::rss_summary::{Article, ArticlesData, ArticlesResponse, Category, CategoryDetails};
+
+/// JSONの修正を依頼するプロンプト
+const REPAIR_PROMPT: &str = "前回の出力はJSONとして解析できませんでした。\n\
+以下のエラーと前回の出力をもとに、JSON Schemaに従った正しいJSONのみを出力してください。\n\
+説明文やcode blockは含めないでください。";
+
+/// AIによる要約に失敗した場合に使用するカテゴリ名
+const FALLBACK_CATEGORY: &str = "新着記事";
+
+/// AIによる要約に失敗した場合の記事の説明の最大文字数
+const FALLBACK_DESCRIPTION_LENGTH: usize = 200;
+
+/// プロンプトが設定されていない場合に使用する組み込みのプロンプトテンプレート
+const DEFAULT_PROMPT_TEMPLATE: &str = include_str!("../../prompts/default_summary.txt");
+
+/// 要約の言語のデフォルト値
+const DEFAULT_LANGUAGE: &str = "日本語";
+
+/// プロンプトを除いたRSSデータに割り当てるトークン数の最低値
+const MIN_ITEMS_TOKEN_BUDGET: usize = 1000;
 
 /// RSSサマリーサービスの実装
-pub struct RssSummaryServiceImpl<T: HttpClient> {
-    http_client: T,
+pub struct RssSummaryServiceImpl<A: AiService, C: SummaryCache> {
+    ai_service: A,
+    summary_cache: C,
+    max_repair_attempts: usize,
+    token_budget: usize,
 }
 
-impl<T: HttpClient> RssSummaryServiceImpl<T> {
+impl<A: AiService, C: SummaryCache> RssSummaryServiceImpl<A, C> {
     /// 新しいRSSサマリーサービスを作成する
     ///
     /// # Arguments
-    /// * `http_client` - HTTPクライアント
-    pub fn new(http_client: T) -> Self {
-        Self { http_client }
+    /// * `ai_service` - 要約に使用するAIサービス
+    /// * `summary_cache` - 生成済みのサマリーのキャッシュ
+    /// * `max_repair_attempts` - 出力が不正なJSONだった場合に修正を依頼する最大回数
+    /// * `token_budget` - 1回のリクエストに含める入力トークン数の上限
+    pub fn new(ai_service: A, summary_cache: C, max_repair_attempts: usize, token_budget: usize) -> Self {
+        Self {
+            ai_service,
+            summary_cache,
+            max_repair_attempts,
+            token_budget,
+        }
     }
 
     /// Base64エンコードされた設定を取得する
@@ -41,6 +77,63 @@ impl<T: HttpClient> RssSummaryServiceImpl<T> {
         Ok(Some(config_str))
     }
 
+    /// フィードに対応するプロンプトテンプレートを取得する
+    ///
+    /// フィードごとのテンプレート、`PROMPT_TEMPLATE_PATH`、`SUMMARY_PROMPT` の順に探し、
+    /// どれも設定されていない場合は組み込みのテンプレートを使用する
+    ///
+    /// # Arguments
+    /// * `feed` - 要約するフィードの設定
+    fn load_prompt_template(feed: &Feed) -> Result<PromptTemplate, RssSummaryError> {
+        let template_path = feed
+            .prompt_template
+            .clone()
+            .or_else(|| env::var("PROMPT_TEMPLATE_PATH").ok());
+        if let Some(path) = template_path {
+            return fs::read_to_string(&path)
+                .map(PromptTemplate::new)
+                .map_err(|e| RssSummaryError::Template(format!("{}: {}", path, e)));
+        }
+
+        // 後方互換のため、Base64エンコードされたプロンプトもテンプレートとして扱う
+        match Self::get_decoded_config() {
+            Ok(Some(p)) => Ok(PromptTemplate::new(p)),
+            Ok(None) => {
+                info!("No summary prompt configured, using the built-in prompt");
+                Ok(PromptTemplate::new(DEFAULT_PROMPT_TEMPLATE.to_string()))
+            }
+            Err(e) => {
+                error!("Error decoding config: {}", e);
+                Err(RssSummaryError::Summary(e.to_string()))
+            }
+        }
+    }
+
+    /// プロンプトテンプレートに埋め込む変数を作成する
+    ///
+    /// # Arguments
+    /// * `rss_channel` - RSSチャンネルデータ
+    /// * `feed` - 要約するフィードの設定
+    fn create_prompt_variables(rss_channel: &Channel, feed: &Feed) -> PromptVariables {
+        let feed_title = if rss_channel.title.is_empty() {
+            feed.name.clone()
+        } else {
+            rss_channel.title.clone()
+        };
+        let language = feed
+            .language
+            .clone()
+            .or_else(|| env::var("SUMMARY_LANGUAGE").ok())
+            .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+
+        PromptVariables {
+            feed_title,
+            language,
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            max_categories: feed.max_categories.unwrap_or(feed.notification_limit),
+        }
+    }
+
     /// RSSデータをモデルに変換する
     fn convert_to_rss_data(rss_channel: &Channel) -> Vec<RssData> {
         rss_channel
@@ -54,93 +147,502 @@ impl<T: HttpClient> RssSummaryServiceImpl<T> {
             .collect()
     }
 
-    /// Gemini APIのURLを取得する
-    fn get_gemini_api_url() -> Result<String, RssSummaryError> {
-        let url = env::var("GEMINI_API_URL").unwrap_or_else(|_| {
-            error!("GEMINI_API_URL is not set");
-            String::new()
-        });
+    /// AIリクエストを作成する
+    ///
+    /// テンプレートに `{{items_json}}` がない場合は、記事一覧をプロンプトの後に続けて渡す
+    fn create_ai_request(
+        template: &PromptTemplate,
+        variables: &PromptVariables,
+        rss_data: &[RssData],
+    ) -> Result<AiRequest, RssSummaryError> {
+        let rss_data_str = serde_json::to_string(rss_data)?;
 
-        if url.is_empty() {
-            return Err(RssSummaryError::EnvVarError(
-                "GEMINI_API_URL is empty".to_string(),
-            ));
+        let (prompt, context) = if template.has_items_placeholder() {
+            (template.render(variables, &rss_data_str)?, String::new())
+        } else {
+            (template.render(variables, "")?, rss_data_str)
+        };
+
+        Ok(AiRequest {
+            prompt,
+            context,
+            response_schema: Some(ArticlesResponse::json_schema()),
+        })
+    }
+
+    /// JSONの修正を依頼するAIリクエストを作成する
+    ///
+    /// # Arguments
+    /// * `content` - 解析できなかったAIの出力
+    /// * `parse_error` - 解析時のエラー
+    fn create_repair_request(content: &str, parse_error: &serde_json::Error) -> AiRequest {
+        let schema = ArticlesResponse::json_schema();
+
+        AiRequest {
+            prompt: format!(
+                "{}\nJSON Schema: {}\nエラー: {}\n前回の出力:",
+                REPAIR_PROMPT, schema, parse_error
+            ),
+            context: content.to_string(),
+            response_schema: Some(schema),
         }
+    }
 
-        Ok(url)
+    /// AIの出力からサマリーを抽出する
+    ///
+    /// 構造化出力に対応していないバックエンドのために、code blockで囲まれた出力も受け付ける
+    fn extract_summary_from_content(content: &str) -> Result<ArticlesResponse, serde_json::Error> {
+        let trimmed = content.trim();
+        let json = trimmed
+            .strip_prefix("```json")
+            .or_else(|| trimmed.strip_prefix("```"))
+            .and_then(|rest| rest.strip_suffix("```"))
+            .unwrap_or(trimmed);
+
+        serde_json::from_str::<ArticlesResponse>(json)
     }
 
-    /// Gemini APIリクエストを作成する
-    fn create_gemini_request(prompt: &str, rss_data: &[RssData]) -> Result<GeminiRequest, RssSummaryError> {
-        let rss_data_str = serde_json::to_string(rss_data)?;
-        
-        Ok(GeminiRequest {
-            contents: vec![Content {
-                parts: vec![Part {
-                    text: format!("{}{}", prompt, rss_data_str),
-                }],
-            }],
-        })
+    /// AIの出力からサマリーを抽出し、解析できない場合はAIに修正を依頼する
+    ///
+    /// 修正を指定回数依頼しても解析できない場合は `None` を返す
+    ///
+    /// # Arguments
+    /// * `content` - AIの出力
+    async fn extract_summary_with_repair(&self, content: String) -> Option<ArticlesResponse> {
+        let mut content = content;
+
+        for attempt in 0..=self.max_repair_attempts {
+            let parse_error = match Self::extract_summary_from_content(&content) {
+                Ok(summary) => return Some(summary),
+                Err(e) => e,
+            };
+            error!("Failed to parse summary: {}", parse_error);
+
+            if attempt == self.max_repair_attempts {
+                break;
+            }
+
+            warn!(
+                "Requesting corrected JSON from AI (attempt {}/{})",
+                attempt + 1,
+                self.max_repair_attempts
+            );
+            let repair_request = Self::create_repair_request(&content, &parse_error);
+            content = match self.ai_service.process_request(repair_request).await {
+                Ok(response) => response.content,
+                Err(e) => {
+                    error!("Failed to request corrected JSON: {}", e);
+                    return None;
+                }
+            };
+        }
+
+        None
     }
 
-    /// レスポンスからサマリーを抽出する
-    fn extract_summary_from_response(response: &GeminiResponse) -> Result<ArticlesResponse, RssSummaryError> {
-        let summary = response
-            .candidates
+    /// AIによる要約に失敗した場合に、記事の一覧をそのままサマリーとして作成する
+    ///
+    /// # Arguments
+    /// * `rss_data` - RSSデータのリスト
+    fn create_fallback_summary(rss_data: &[RssData]) -> ArticlesResponse {
+        let articles: Vec<Article> = rss_data
             .iter()
-            .filter_map(|candidate| {
-                candidate.content.parts.iter().find_map(|part| {
-                    // code blockを削除
-                    let part_text = part.text.replace("```json", "").replace("```", "");
-                    match serde_json::from_str::<ArticlesResponse>(&part_text) {
-                        Ok(summary) => Some(summary),
-                        Err(e) => {
-                            error!("Failed to parse summary: {}", e);
-                            None
+            .filter_map(|item| {
+                let link = item.link.clone()?;
+                let description = item
+                    .description
+                    .as_deref()
+                    .map(|description| {
+                        let mut chars = description.chars();
+                        let truncated: String = chars.by_ref().take(FALLBACK_DESCRIPTION_LENGTH).collect();
+                        if chars.next().is_some() {
+                            format!("{}…", truncated)
+                        } else {
+                            truncated
                         }
-                    }
+                    })
+                    .unwrap_or_default();
+
+                Some(Article {
+                    title: item.title.clone().unwrap_or_else(|| link.clone()),
+                    description,
+                    link,
                 })
             })
-            .next();
+            .collect();
 
-        summary.ok_or_else(|| {
-            RssSummaryError::SummaryError("Failed to extract summary from response".to_string())
-        })
+        let total = articles.len();
+        let category = Category {
+            category_map: HashMap::from([(
+                FALLBACK_CATEGORY.to_string(),
+                CategoryDetails {
+                    category_count: Some(total),
+                    articles,
+                },
+            )]),
+        };
+
+        ArticlesResponse::new(
+            "AIによる要約に失敗したため、新着記事の一覧をお届けします".to_string(),
+            ArticlesData::new(total, vec![category]),
+        )
+    }
+}
+
+impl<A: AiService, C: SummaryCache> RssSummaryServiceImpl<A, C> {
+    /// キャッシュのキーを作成する
+    ///
+    /// プロンプト・モデル・記事一覧が同じであれば同じキーになる
+    ///
+    /// # Arguments
+    /// * `request` - AIリクエスト
+    fn cache_key(&self, request: &AiRequest) -> String {
+        let model_name = self.ai_service.model_name();
+        let mut hasher = Sha256::new();
+        for part in [request.prompt.as_str(), model_name.as_str(), request.context.as_str()].iter() {
+            hasher.update(part.as_bytes());
+            hasher.update([0u8]);
+        }
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// RSSデータのチャンクを要約する
+    ///
+    /// # Arguments
+    /// * `template` - 要約のプロンプトテンプレート
+    /// * `variables` - プロンプトテンプレートに埋め込む変数
+    /// * `rss_data` - 要約するRSSデータのリスト
+    async fn summarize_chunk(
+        &self,
+        template: &PromptTemplate,
+        variables: &PromptVariables,
+        rss_data: &[RssData],
+    ) -> Result<ArticlesResponse, RssSummaryError> {
+        // AIリクエストの作成
+        let ai_request = Self::create_ai_request(template, variables, rss_data)?;
+
+        // 同じ内容を要約済みであればAIサービスを呼ばない
+        let cache_key = self.cache_key(&ai_request);
+        if let Some(summary) = self.summary_cache.get(&cache_key) {
+            info!("Using cached summary for {} items", rss_data.len());
+            return Ok(summary);
+        }
+
+        // AIサービスへのリクエスト
+        let response = self.ai_service.process_request(ai_request).await?;
+
+        // レスポンスからサマリーを抽出し、失敗した場合は記事の一覧で代用する
+        // (記事の一覧は次回に改めて要約できるようキャッシュしない)
+        match self.extract_summary_with_repair(response.content).await {
+            Some(summary) => {
+                self.summary_cache.put(&cache_key, &summary);
+                Ok(summary)
+            }
+            None => {
+                warn!("Falling back to a plain listing of {} items", rss_data.len());
+                Ok(Self::create_fallback_summary(rss_data))
+            }
+        }
     }
 }
 
-impl<T: HttpClient + Send + Sync + 'static> RssSummaryService for RssSummaryServiceImpl<T> {
-    async fn fetch_summary(&self, rss_channel: &Channel) -> Result<ArticlesResponse, RssSummaryError> {
+impl<A: AiService, C: SummaryCache> RssSummaryService for RssSummaryServiceImpl<A, C> {
+    async fn fetch_summary(&self, rss_channel: &Channel, feed: &Feed) -> Result<ArticlesResponse, RssSummaryError> {
         // RSSデータをモデルに変換
         let rss_data_items = Self::convert_to_rss_data(rss_channel);
 
-        // プロンプトの取得
-        let prompt = match Self::get_decoded_config() {
-            Ok(Some(p)) => p,
-            Ok(None) => {
-                warn!("No summary prompt found, using empty prompt");
-                return Err(RssSummaryError::SummaryError("using empty prompt.".to_string()));
-            }
-            Err(e) => {
-                error!("Error decoding config: {}", e);
-                return Err(RssSummaryError::SummaryError(e.to_string()));
+        // プロンプトテンプレートの取得
+        let template = Self::load_prompt_template(feed)?;
+        let variables = Self::create_prompt_variables(rss_channel, feed);
+
+        // プロンプトを除いたトークン数の上限でRSSデータを分割
+        let items_budget = self
+            .token_budget
+            .saturating_sub(estimate_tokens(&template.render(&variables, "")?))
+            .max(MIN_ITEMS_TOKEN_BUDGET);
+        let chunks = split_into_chunks(&rss_data_items, items_budget);
+        if chunks.len() > 1 {
+            info!(
+                "Splitting {} items into {} chunks (token budget: {})",
+                rss_data_items.len(),
+                chunks.len(),
+                items_budget
+            );
+        }
+
+        // チャンクごとに要約し、1つのサマリーにまとめる
+        let mut summaries = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            summaries.push(self.summarize_chunk(&template, &variables, chunk).await?);
+        }
+
+        if summaries.len() == 1 {
+            Ok(summaries.remove(0))
+        } else {
+            Ok(merge_summaries(summaries, variables.max_categories))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rss::{ChannelBuilder, ItemBuilder};
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::domain::ai_service::{AiResponse, AiServiceError};
+    use crate::infrastructure::cache::summary_cache::TtlSummaryCache;
+
+    /// 決められた出力を順番に返すAIサービス
+    struct FakeAiService {
+        responses: Mutex<VecDeque<String>>,
+        requests: Mutex<Vec<AiRequest>>,
+    }
+
+    impl FakeAiService {
+        fn new(responses: &[&str]) -> Self {
+            Self {
+                responses: Mutex::new(responses.iter().map(|response| response.to_string()).collect()),
+                requests: Mutex::new(Vec::new()),
             }
-        };
+        }
 
-        // Gemini API URLの取得
-        let url = Self::get_gemini_api_url()?;
+        fn request_count(&self) -> usize {
+            self.requests.lock().unwrap().len()
+        }
+    }
+
+    impl AiService for FakeAiService {
+        async fn process_request(&self, request: AiRequest) -> Result<AiResponse, AiServiceError> {
+            self.requests.lock().unwrap().push(request);
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .map(|content| AiResponse { content })
+                .ok_or_else(|| AiServiceError::ResponseError("no more responses".to_string()))
+        }
+
+        fn model_name(&self) -> String {
+            "fake".to_string()
+        }
+    }
+
+    fn service(
+        responses: &[&str],
+        cache: TtlSummaryCache,
+        token_budget: usize,
+    ) -> RssSummaryServiceImpl<FakeAiService, TtlSummaryCache> {
+        RssSummaryServiceImpl::new(FakeAiService::new(responses), cache, 2, token_budget)
+    }
+
+    fn no_cache() -> TtlSummaryCache {
+        TtlSummaryCache::new(Duration::ZERO, None)
+    }
+
+    fn channel(items: &[(&str, &str, &str)]) -> Channel {
+        ChannelBuilder::default()
+            .title("Test feed")
+            .items(
+                items
+                    .iter()
+                    .map(|(title, description, link)| {
+                        ItemBuilder::default()
+                            .title(Some(title.to_string()))
+                            .description(Some(description.to_string()))
+                            .link(Some(link.to_string()))
+                            .build()
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .build()
+    }
+
+    fn feed() -> Feed {
+        Feed::new("https://example.com/feed".to_string(), "Example".to_string(), 10)
+    }
+
+    fn summary_json(categories: &[(&str, &[&str])]) -> String {
+        let summary: Vec<serde_json::Value> = categories
+            .iter()
+            .map(|(name, links)| {
+                serde_json::json!({
+                    "category": name,
+                    "category_count": links.len(),
+                    "articles": links
+                        .iter()
+                        .map(|link| serde_json::json!({ "title": link, "description": "", "link": link }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let total: usize = categories.iter().map(|(_, links)| links.len()).sum();
+
+        serde_json::json!({ "message": "summary", "data": { "total": total, "summary": summary } }).to_string()
+    }
+
+    fn category_names(summary: &ArticlesResponse) -> Vec<String> {
+        summary
+            .data
+            .summary
+            .iter()
+            .flat_map(|category| category.category_map.keys().cloned())
+            .collect()
+    }
+
+    #[actix_web::test]
+    async fn summarizes_items_with_a_single_request() {
+        let response = summary_json(&[("Rust", &["https://example.com/1"])]);
+        let service = service(&[&response], no_cache(), 100_000);
+        let channel = channel(&[("One", "first", "https://example.com/1")]);
+
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        assert_eq!(service.ai_service.request_count(), 1);
+        assert_eq!(category_names(&summary), vec!["Rust"]);
+        assert_eq!(summary.data.total, 1);
+    }
+
+    #[actix_web::test]
+    async fn accepts_output_wrapped_in_a_code_block() {
+        let response = format!("```json\n{}\n```", summary_json(&[("Rust", &["https://example.com/1"])]));
+        let service = service(&[&response], no_cache(), 100_000);
+        let channel = channel(&[("One", "first", "https://example.com/1")]);
+
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        assert_eq!(category_names(&summary), vec!["Rust"]);
+    }
+
+    #[actix_web::test]
+    async fn requests_corrected_json_when_output_is_invalid() {
+        let repaired = summary_json(&[("Rust", &["https://example.com/1"])]);
+        let service = service(&["not json", &repaired], no_cache(), 100_000);
+        let channel = channel(&[("One", "first", "https://example.com/1")]);
+
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        let requests = service.ai_service.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].prompt.starts_with(REPAIR_PROMPT));
+        assert_eq!(requests[1].context, "not json");
+        assert_eq!(category_names(&summary), vec!["Rust"]);
+    }
+
+    #[actix_web::test]
+    async fn falls_back_to_a_plain_listing_when_repairs_are_exhausted() {
+        let service = service(&["not json", "still not json", "nope"], no_cache(), 100_000);
+        let channel = channel(&[
+            ("One", "first", "https://example.com/1"),
+            ("Two", "second", "https://example.com/2"),
+        ]);
+
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        assert_eq!(service.ai_service.request_count(), 3);
+        assert_eq!(category_names(&summary), vec![FALLBACK_CATEGORY]);
+        assert_eq!(summary.data.total, 2);
+        let articles = &summary.data.summary[0].category_map[FALLBACK_CATEGORY].articles;
+        assert_eq!(articles[0].title, "One");
+        assert_eq!(articles[1].link, "https://example.com/2");
+    }
+
+    #[actix_web::test]
+    async fn merges_summaries_of_each_chunk() {
+        // 1件で約600トークンになる説明にして、最低値の1000トークンに2件が収まらないようにする
+        let description = "あ".repeat(600);
+        let channel = channel(&[
+            ("One", &description, "https://example.com/1"),
+            ("Two", &description, "https://example.com/2"),
+            ("Three", &description, "https://example.com/3"),
+        ]);
+        let first = summary_json(&[("Rust", &["https://example.com/1"])]);
+        let second = summary_json(&[("Go", &["https://example.com/2"])]);
+        let third = summary_json(&[("Rust", &["https://example.com/3"])]);
+        let service = service(&[&first, &second, &third], no_cache(), 0);
+
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        assert_eq!(service.ai_service.request_count(), 3);
+        assert_eq!(category_names(&summary), vec!["Rust", "Go"]);
+        assert_eq!(summary.data.summary[0].category_map["Rust"].articles.len(), 2);
+        assert_eq!(summary.data.total, 3);
+    }
+
+    #[actix_web::test]
+    async fn keeps_categories_with_the_most_articles_after_merging() {
+        let description = "あ".repeat(600);
+        let channel = channel(&[
+            ("One", &description, "https://example.com/1"),
+            ("Two", &description, "https://example.com/2"),
+            ("Three", &description, "https://example.com/3"),
+        ]);
+        let first = summary_json(&[("Go", &["https://example.com/1"])]);
+        let second = summary_json(&[("Rust", &["https://example.com/2"])]);
+        let third = summary_json(&[("Rust", &["https://example.com/3"])]);
+        let service = service(&[&first, &second, &third], no_cache(), 0);
+        let mut feed = feed();
+        feed.max_categories = Some(1);
+
+        let summary = service.fetch_summary(&channel, &feed).await.unwrap();
+
+        assert_eq!(category_names(&summary), vec!["Rust"]);
+        assert_eq!(summary.data.total, 2);
+    }
+
+    #[actix_web::test]
+    async fn uses_cached_summary_for_the_same_items() {
+        let response = summary_json(&[("Rust", &["https://example.com/1"])]);
+        let cache = TtlSummaryCache::new(Duration::from_secs(3600), None);
+        let service = service(&[&response], cache, 100_000);
+        let channel = channel(&[("One", "first", "https://example.com/1")]);
+
+        service.fetch_summary(&channel, &feed()).await.unwrap();
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        assert_eq!(service.ai_service.request_count(), 1);
+        assert_eq!(category_names(&summary), vec!["Rust"]);
+    }
+
+    #[actix_web::test]
+    async fn does_not_cache_the_fallback_summary() {
+        let response = summary_json(&[("Rust", &["https://example.com/1"])]);
+        let cache = TtlSummaryCache::new(Duration::from_secs(3600), None);
+        let service = service(&["a", "b", "c", &response], cache, 100_000);
+        let channel = channel(&[("One", "first", "https://example.com/1")]);
+
+        service.fetch_summary(&channel, &feed()).await.unwrap();
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
+
+        assert_eq!(service.ai_service.request_count(), 4);
+        assert_eq!(category_names(&summary), vec!["Rust"]);
+    }
 
-        // Gemini APIリクエストの作成
-        let gemini_request_body = Self::create_gemini_request(&prompt, &rss_data_items)?;
+    #[actix_web::test]
+    async fn summarizes_again_after_the_cache_expires() {
+        let first = summary_json(&[("Rust", &["https://example.com/1"])]);
+        let second = summary_json(&[("Go", &["https://example.com/1"])]);
+        let now = Arc::new(AtomicU64::new(1_000));
+        let clock = Arc::clone(&now);
+        let cache = TtlSummaryCache::with_clock(Duration::from_secs(60), None, move || clock.load(Ordering::SeqCst));
+        let service = service(&[&first, &second], cache, 100_000);
+        let channel = channel(&[("One", "first", "https://example.com/1")]);
 
-        // Gemini APIへのリクエスト
-        let response: GeminiResponse = self
-            .http_client
-            .post_with_response(&url, &gemini_request_body)
-            .await
-            .map_err(|e| RssSummaryError::HttpError(e.to_string()))?;
+        service.fetch_summary(&channel, &feed()).await.unwrap();
+        now.store(1_059, Ordering::SeqCst);
+        service.fetch_summary(&channel, &feed()).await.unwrap();
+        now.store(1_060, Ordering::SeqCst);
+        let summary = service.fetch_summary(&channel, &feed()).await.unwrap();
 
-        // レスポンスからサマリーを抽出
-        Self::extract_summary_from_response(&response)
+        assert_eq!(service.ai_service.request_count(), 2);
+        assert_eq!(category_names(&summary), vec!["Go"]);
     }
 }
@@ -0,0 +1,175 @@
+use log::warn;
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::model::rss_data::RssData;
+use crate::domain::model::rss_summary::{ArticlesData, ArticlesResponse, Category, CategoryDetails};
+
+/// 説明を切り詰める場合に最低限残す文字数
+const MIN_DESCRIPTION_LENGTH: usize = 50;
+
+/// テキストのトークン数を概算する
+///
+/// 英数字はおおよそ4文字で1トークン、日本語などの非ASCII文字は1文字で1トークンとして計算する
+///
+/// # Arguments
+/// * `text` - 対象のテキスト
+pub fn estimate_tokens(text: &str) -> usize {
+    let (ascii, non_ascii) = text
+        .chars()
+        .fold((0usize, 0usize), |(ascii, non_ascii), c| {
+            if c.is_ascii() {
+                (ascii + 1, non_ascii)
+            } else {
+                (ascii, non_ascii + 1)
+            }
+        });
+    ascii.div_ceil(4) + non_ascii
+}
+
+/// RSSデータ1件のトークン数を概算する
+///
+/// # Arguments
+/// * `item` - RSSデータ
+fn estimate_item_tokens(item: &RssData) -> usize {
+    serde_json::to_string(item)
+        .map(|json| estimate_tokens(&json))
+        .unwrap_or(0)
+}
+
+/// RSSデータをトークン数の上限に収まるチャンクに分割する
+///
+/// 1件だけで上限を超えるアイテムは、上限に収まるよう説明を切り詰める
+///
+/// # Arguments
+/// * `items` - RSSデータのリスト
+/// * `token_budget` - 1チャンクあたりのトークン数の上限
+pub fn split_into_chunks(items: &[RssData], token_budget: usize) -> Vec<Vec<RssData>> {
+    let mut chunks = Vec::new();
+    let mut current = Vec::new();
+    let mut current_tokens = 0;
+
+    for item in items {
+        let item = fit_item_to_budget(item, token_budget);
+        let item_tokens = estimate_item_tokens(&item);
+
+        if !current.is_empty() && current_tokens + item_tokens > token_budget {
+            chunks.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+
+        current_tokens += item_tokens;
+        current.push(item);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// 1件だけで上限を超えるアイテムの説明を切り詰める
+///
+/// # Arguments
+/// * `item` - RSSデータ
+/// * `token_budget` - トークン数の上限
+fn fit_item_to_budget(item: &RssData, token_budget: usize) -> RssData {
+    let item_tokens = estimate_item_tokens(item);
+    let Some(description) = item.description.as_deref() else {
+        return item.clone();
+    };
+    if item_tokens <= token_budget {
+        return item.clone();
+    }
+
+    // 超過分のトークン数だけ説明を短くする (1文字1トークンとして安全側に見積もる)
+    let overflow = item_tokens - token_budget;
+    let length = description
+        .chars()
+        .count()
+        .saturating_sub(overflow)
+        .max(MIN_DESCRIPTION_LENGTH);
+    warn!(
+        "Item {:?} exceeds the token budget ({} > {}), truncating its description to {} characters",
+        item.link, item_tokens, token_budget, length
+    );
+
+    let mut truncated = item.clone();
+    truncated.description = Some(format!(
+        "{}…",
+        description.chars().take(length).collect::<String>()
+    ));
+    truncated
+}
+
+/// チャンクごとのサマリーを1つのサマリーにまとめる
+///
+/// 同じ名前のカテゴリは1つにまとめ、同じリンクの記事は重複して含めない。
+/// まとめた結果がカテゴリ数の上限を超える場合は、記事の多いカテゴリを残す
+///
+/// # Arguments
+/// * `summaries` - チャンクごとのサマリー
+/// * `max_categories` - カテゴリ数の上限
+pub fn merge_summaries(summaries: Vec<ArticlesResponse>, max_categories: usize) -> ArticlesResponse {
+    let message = summaries
+        .first()
+        .map(|summary| summary.message.clone())
+        .unwrap_or_default();
+
+    // カテゴリの登場順を保ったまま、同じ名前のカテゴリをまとめる
+    let mut order: Vec<String> = Vec::new();
+    let mut merged: HashMap<String, CategoryDetails> = HashMap::new();
+    let mut seen_links: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for category in summaries.into_iter().flat_map(|summary| summary.data.summary) {
+        for (name, details) in category.category_map {
+            let name = name.trim().to_string();
+            let entry = merged.entry(name.clone()).or_insert_with(|| {
+                order.push(name.clone());
+                CategoryDetails {
+                    category_count: Some(0),
+                    articles: Vec::new(),
+                }
+            });
+            let links = seen_links.entry(name).or_default();
+
+            for article in details.articles {
+                if links.insert(article.link.clone()) {
+                    entry.articles.push(article);
+                }
+            }
+            entry.category_count = Some(entry.articles.len());
+        }
+    }
+
+    // 上限を超える場合は記事の少ないカテゴリから除外する (同数の場合は後に登場したものを除外する)
+    if order.len() > max_categories {
+        let mut ranked = order.clone();
+        ranked.sort_by_key(|name| std::cmp::Reverse(merged[name].article_count()));
+        for name in &ranked[max_categories..] {
+            warn!(
+                "Merged summary exceeds {} categories, dropping '{}' with {} articles",
+                max_categories,
+                name,
+                merged[name].article_count()
+            );
+            merged.remove(name);
+        }
+    }
+
+    let categories: Vec<Category> = order
+        .into_iter()
+        .filter_map(|name| {
+            merged.remove(&name).map(|details| Category {
+                category_map: HashMap::from([(name, details)]),
+            })
+        })
+        .collect();
+    let total = categories
+        .iter()
+        .flat_map(|category| category.category_map.values())
+        .map(|details| details.article_count())
+        .sum();
+
+    ArticlesResponse::new(message, ArticlesData::new(total, categories))
+}
@@ -0,0 +1,84 @@
+use log::warn;
+use std::collections::HashSet;
+
+use crate::domain::model::rss_data::RssData;
+use crate::domain::model::rss_summary::{Article, ArticlesData, ArticlesResponse, Category};
+
+/// サマリーの検証結果を表す構造体
+#[derive(Debug, Clone)]
+pub struct ValidatedSummary {
+    /// 検証済みのサマリー
+    pub summary: ArticlesResponse,
+    /// 入力に存在しないリンクを持っていたため除外した記事
+    pub dropped_articles: Vec<Article>,
+}
+
+/// AIが生成したサマリーを入力のRSSデータと照合して検証する
+///
+/// 入力に存在しないリンクを持つ記事を除外し、記事がなくなったカテゴリを取り除いた上で、
+/// `total` と `category_count` を実際の記事数に合わせる
+///
+/// # Arguments
+/// * `summary` - AIが生成したサマリー
+/// * `rss_data` - 要約の入力としたRSSデータのリスト
+pub fn validate_summary(summary: ArticlesResponse, rss_data: &[RssData]) -> ValidatedSummary {
+    let known_links: HashSet<String> = rss_data
+        .iter()
+        .filter_map(|item| item.link.as_deref())
+        .map(normalize_link)
+        .collect();
+
+    let mut dropped_articles = Vec::new();
+    let categories: Vec<Category> = summary
+        .data
+        .summary
+        .into_iter()
+        .filter_map(|mut category| {
+            for (name, details) in category.category_map.iter_mut() {
+                let (articles, dropped): (Vec<Article>, Vec<Article>) = details
+                    .articles
+                    .drain(..)
+                    .partition(|article| known_links.contains(&normalize_link(&article.link)));
+
+                for article in &dropped {
+                    warn!(
+                        "Dropping article '{}' in category '{}': link {} is not in the feed",
+                        article.title, name, article.link
+                    );
+                }
+                dropped_articles.extend(dropped);
+
+                details.category_count = Some(articles.len());
+                details.articles = articles;
+            }
+
+            category
+                .category_map
+                .retain(|_, details| !details.articles.is_empty());
+            if category.category_map.is_empty() {
+                None
+            } else {
+                Some(category)
+            }
+        })
+        .collect();
+
+    let total = categories
+        .iter()
+        .flat_map(|category| category.category_map.values())
+        .map(|details| details.article_count())
+        .sum();
+
+    ValidatedSummary {
+        summary: ArticlesResponse::new(summary.message, ArticlesData::new(total, categories)),
+        dropped_articles,
+    }
+}
+
+/// 比較のためにリンクを正規化する
+///
+/// # Arguments
+/// * `link` - リンク
+pub fn normalize_link(link: &str) -> String {
+    link.trim().trim_end_matches('/').to_string()
+}
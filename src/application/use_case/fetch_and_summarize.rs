@@ -1,28 +1,43 @@
-use log::{error, info};
+use log::{error, info, warn};
+use rss::Channel;
+use serde::Serialize;
 
+use crate::application::notification_metadata::NotificationMetadataBuilder;
+use crate::application::summary_validator::{normalize_link, validate_summary};
+use crate::domain::clock::unix_now;
 use crate::domain::event::rss_events::{EventPublisher, RssEvent};
+use crate::domain::model::feed::Feed;
+use crate::domain::model::outbox::{OutboxEntry, RetryPolicy};
 use crate::domain::model::rss_summary::ArticlesResponse;
 use crate::domain::notification::{Notification, NotificationField, NotificationService};
-use crate::domain::repository::rss_repository::RssRepository;
+use crate::domain::repository::outbox_repository::OutboxRepository;
+use crate::domain::repository::rss_repository::{FeedFetchResult, RssRepository};
+use crate::domain::repository::seen_item_repository::{seen_key, SeenItemRepository};
 use crate::domain::rss_summary::{RssSummaryError, RssSummaryService};
 
+use std::collections::HashSet;
 use std::fmt;
 use std::error::Error;
+use std::sync::Mutex;
 
 /// アプリケーションエラー型
 #[derive(Debug)]
 pub enum AppError {
-    RssError(String),
-    SummaryError(String),
-    NotificationError(String),
+    Rss(String),
+    Summary(String),
+    Notification(String),
+    SeenItemStore(String),
+    Outbox(String),
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            AppError::RssError(e) => write!(f, "RSS error: {}", e),
-            AppError::SummaryError(e) => write!(f, "Summary error: {}", e),
-            AppError::NotificationError(e) => write!(f, "Notification error: {}", e),
+            AppError::Rss(e) => write!(f, "RSS error: {}", e),
+            AppError::Summary(e) => write!(f, "Summary error: {}", e),
+            AppError::Notification(e) => write!(f, "Notification error: {}", e),
+            AppError::SeenItemStore(e) => write!(f, "Seen item store error: {}", e),
+            AppError::Outbox(e) => write!(f, "Outbox error: {}", e),
         }
     }
 }
@@ -31,30 +46,120 @@ impl Error for AppError {}
 
 impl From<RssSummaryError> for AppError {
     fn from(error: RssSummaryError) -> Self {
-        AppError::SummaryError(error.to_string())
+        AppError::Summary(error.to_string())
+    }
+}
+
+/// ユースケースの実行結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    /// 新しいアイテムを要約して通知した
+    Notified { item_count: usize },
+    /// 新しいアイテムを要約したが、通知に失敗したためアウトボックスに保存した
+    NotificationQueued { item_count: usize },
+    /// 新しいアイテムがなかった
+    NoNewItems,
+    /// 同じフィードの前回の実行が終わっていないため実行しなかった
+    AlreadyRunning,
+}
+
+impl ExecutionOutcome {
+    /// 通知した新しいアイテムの数を取得する
+    pub fn new_item_count(&self) -> usize {
+        match self {
+            ExecutionOutcome::Notified { item_count }
+            | ExecutionOutcome::NotificationQueued { item_count } => *item_count,
+            ExecutionOutcome::NoNewItems | ExecutionOutcome::AlreadyRunning => 0,
+        }
+    }
+}
+
+/// フィードごとの実行結果
+#[derive(Debug)]
+pub struct FeedExecutionResult {
+    pub feed: Feed,
+    pub result: Result<ExecutionOutcome, AppError>,
+}
+
+impl FeedExecutionResult {
+    /// 実行が成功したかどうかを確認する
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// アウトボックスの再送結果
+#[derive(Debug, Default, Serialize)]
+pub struct OutboxRetryReport {
+    /// 送信できたエントリーの数
+    pub delivered: usize,
+    /// 再び送信に失敗したエントリーの数
+    pub failed: usize,
+    /// アウトボックスに残っているエントリーの数
+    pub remaining: usize,
+}
+
+/// 実行中のフィードやアウトボックスのエントリーを登録し、スコープを抜けたときに登録を解除するガード
+struct RunningGuard<'a> {
+    running: &'a Mutex<HashSet<String>>,
+    key: String,
+}
+
+impl<'a> RunningGuard<'a> {
+    /// キーを実行中として登録する
+    ///
+    /// 既に実行中の場合は `None` を返す
+    ///
+    /// # Arguments
+    /// * `running` - 実行中のキーの集合
+    /// * `key` - フィードのURLまたはエントリーのID
+    fn acquire(running: &'a Mutex<HashSet<String>>, key: &str) -> Option<Self> {
+        let mut keys = running.lock().unwrap();
+        if !keys.insert(key.to_string()) {
+            return None;
+        }
+        Some(Self {
+            running,
+            key: key.to_string(),
+        })
+    }
+}
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.running.lock().unwrap().remove(&self.key);
     }
 }
 
 /// RSSフィードを取得し、要約して通知するユースケース
-pub struct FetchAndSummarizeUseCase<R, S, N, E>
+pub struct FetchAndSummarizeUseCase<R, S, N, E, K, O>
 where
     R: RssRepository,
     S: RssSummaryService,
     N: NotificationService,
     E: EventPublisher,
+    K: SeenItemRepository,
+    O: OutboxRepository,
 {
     rss_repository: R,
     summary_service: S,
     notification_service: N,
     event_publisher: E,
+    seen_item_repository: K,
+    outbox_repository: O,
+    retry_policy: RetryPolicy,
+    running_feeds: Mutex<HashSet<String>>,
+    delivering_entries: Mutex<HashSet<String>>,
 }
 
-impl<R, S, N, E> FetchAndSummarizeUseCase<R, S, N, E>
+impl<R, S, N, E, K, O> FetchAndSummarizeUseCase<R, S, N, E, K, O>
 where
     R: RssRepository,
     S: RssSummaryService,
     N: NotificationService,
     E: EventPublisher,
+    K: SeenItemRepository,
+    O: OutboxRepository,
 {
     /// 新しいユースケースを作成する
     ///
@@ -63,46 +168,73 @@ where
     /// * `summary_service` - RSSサマリーサービス
     /// * `notification_service` - 通知サービス
     /// * `event_publisher` - イベントパブリッシャー
+    /// * `seen_item_repository` - 通知済みアイテムのリポジトリ
+    /// * `outbox_repository` - 送信できなかった通知を保存するアウトボックス
+    /// * `retry_policy` - 送信できなかった通知を再送する間隔
     pub fn new(
         rss_repository: R,
         summary_service: S,
         notification_service: N,
         event_publisher: E,
+        seen_item_repository: K,
+        outbox_repository: O,
+        retry_policy: RetryPolicy,
     ) -> Self {
         Self {
             rss_repository,
             summary_service,
             notification_service,
             event_publisher,
+            seen_item_repository,
+            outbox_repository,
+            retry_policy,
+            running_feeds: Mutex::new(HashSet::new()),
+            delivering_entries: Mutex::new(HashSet::new()),
         }
     }
 
     /// RSSフィードを取得し、要約して通知する
     ///
     /// # Arguments
-    /// * `feed_url` - RSSフィードのURL
-    /// * `notification_limit` - 通知の制限数
-    pub async fn execute(
-        &self,
-        feed_url: &str,
-        notification_limit: usize,
-    ) -> Result<(), AppError> {
+    /// * `feed` - 処理対象のフィード
+    pub async fn execute(&self, feed: &Feed) -> Result<ExecutionOutcome, AppError> {
+        let feed_url = feed.url.as_str();
+        let notification_limit = feed.notification_limit;
+
         // RSSフィードの取得
-        let rss_channel = self
+        let fetch_result = self
             .rss_repository
             .fetch_feed(feed_url)
             .await
             .map_err(|e| {
                 error!("Failed to fetch RSS feed: {}", e);
-                AppError::RssError(e.to_string())
+                AppError::Rss(e.to_string())
             })?;
 
+        // 前回から更新されていない場合はサマリーを生成しない
+        let rss_channel = match fetch_result {
+            FeedFetchResult::Fetched(channel) => *channel,
+            FeedFetchResult::NotModified => {
+                info!("Feed {} is not modified since the last fetch", feed_url);
+                return Ok(ExecutionOutcome::NoNewItems);
+            }
+        };
+
         // イベント発行: フィード取得
         self.event_publisher.publish(RssEvent::FeedFetched {
             url: feed_url.to_string(),
-            channel: rss_channel.clone(),
+            channel: Box::new(rss_channel.clone()),
         });
 
+        // 通知済みのアイテムを除外
+        let rss_channel = self.filter_unseen_items(feed_url, rss_channel)?;
+        if rss_channel.items.is_empty() {
+            info!("No new items in {}", feed_url);
+            self.rss_repository.mark_feed_processed(feed_url);
+            return Ok(ExecutionOutcome::NoNewItems);
+        }
+        let item_count = rss_channel.items.len();
+
         // RSSデータをモデルに変換
         let rss_data_items = self.rss_repository.convert_to_rss_data(&rss_channel);
 
@@ -112,44 +244,254 @@ where
         });
 
         // RSSサマリーの取得
-        let summary = self.summary_service.fetch_summary(&rss_channel).await?;
+        let summary = self.summary_service.fetch_summary(&rss_channel, feed).await?;
 
         // イベント発行: サマリー生成
         self.event_publisher.publish(RssEvent::SummaryGenerated {
             summary: summary.clone(),
         });
 
+        // 入力にない記事を除外し、記事数を補正
+        let validated = validate_summary(summary, &rss_data_items);
+
+        // イベント発行: サマリー検証
+        self.event_publisher.publish(RssEvent::SummaryValidated {
+            dropped_articles: validated.dropped_articles,
+        });
+
+        let summary = validated.summary;
+        if summary.data.total_articles() == 0 {
+            error!("No valid articles remain in the summary of {}", feed_url);
+            return Err(AppError::Summary(
+                "summary contains no articles from the feed".to_string(),
+            ));
+        }
+
         // 通知データの作成と制限
-        let notifications = self.create_notifications(&summary, notification_limit);
+        let notifications = self.create_notifications(&summary, &rss_channel, notification_limit);
+
+        // 通知するアイテムのキー
+        // 制限で除外された記事やAIが要約しなかった記事は、次回の実行で改めて要約する
+        let notified_keys = notified_item_keys(&rss_channel, &notifications);
+        let all_notified = notified_keys.len() >= item_count;
+        if !all_notified {
+            info!(
+                "{} of {} new items in {} are not notified and will be summarized again on the next run",
+                item_count - notified_keys.len(),
+                item_count,
+                feed_url
+            );
+        }
 
         // 通知の送信
-        self.notification_service
-            .send_notifications(notifications.clone())
+        // 失敗した場合はサマリーを作り直さずに済むよう、通知をアウトボックスに保存して後で再送する
+        let delivery_id = OutboxEntry::new_id(feed, unix_now());
+        if let Err(e) = self
+            .notification_service
+            .send_notifications(feed_url, &delivery_id, 0, notifications.clone())
             .await
-            .map_err(|e| {
-                error!("Failed to send notifications: {}", e);
-                AppError::NotificationError(e.to_string())
+        {
+            error!("Failed to send notifications: {}", e);
+            let entry = OutboxEntry::new(
+                delivery_id,
+                feed,
+                notifications,
+                &e,
+                &self.retry_policy,
+                unix_now(),
+            );
+            self.outbox_repository.save(&entry).map_err(|outbox_error| {
+                error!("Failed to save notifications to outbox: {}", outbox_error);
+                AppError::Notification(e.to_string())
             })?;
+            warn!(
+                "Saved notifications of {} to outbox as {}, next attempt at {}",
+                feed_url, entry.id, entry.next_attempt_at
+            );
+
+            self.mark_processed(feed_url, &notified_keys, all_notified)?;
+            return Ok(ExecutionOutcome::NotificationQueued {
+                item_count: notified_keys.len(),
+            });
+        }
 
         // イベント発行: 通知送信
         self.event_publisher.publish(RssEvent::NotificationSent {
             count: notifications.len(),
         });
 
+        // 通知が成功したアイテムを通知済みとして記録
+        self.mark_processed(feed_url, &notified_keys, all_notified)?;
+
         info!("Successfully processed RSS feed and sent notifications");
+        Ok(ExecutionOutcome::Notified {
+            item_count: notified_keys.len(),
+        })
+    }
+
+    /// 通知したアイテムを通知済みとして記録し、フィードを処理済みにする
+    ///
+    /// 通知しなかったアイテムが残っている場合は、次回の取得が未更新 (304) にならないよう
+    /// フィードを処理済みにしない
+    ///
+    /// # Arguments
+    /// * `feed_url` - RSSフィードのURL
+    /// * `keys` - 通知したアイテムのキーのリスト
+    /// * `all_notified` - 新しいアイテムをすべて通知したかどうか
+    fn mark_processed(&self, feed_url: &str, keys: &[String], all_notified: bool) -> Result<(), AppError> {
+        self.seen_item_repository
+            .mark_seen(feed_url, keys)
+            .map_err(|e| {
+                error!("Failed to mark items as seen: {}", e);
+                AppError::SeenItemStore(e.to_string())
+            })?;
+        if all_notified {
+            self.rss_repository.mark_feed_processed(feed_url);
+        }
         Ok(())
     }
 
+    /// アウトボックスのエントリーを取得する
+    pub fn outbox_entries(&self) -> Result<Vec<OutboxEntry>, AppError> {
+        self.outbox_repository
+            .list()
+            .map_err(|e| AppError::Outbox(e.to_string()))
+    }
+
+    /// アウトボックスに保存された通知を再送する
+    ///
+    /// フィードの取得や要約はやり直さず、保存された通知をそのまま送信する
+    ///
+    /// # Arguments
+    /// * `force` - `true` の場合は再送時刻になっていないエントリーも送信する
+    pub async fn retry_outbox(&self, force: bool) -> Result<OutboxRetryReport, AppError> {
+        let mut report = OutboxRetryReport::default();
+
+        for mut entry in self.outbox_entries()? {
+            if !force && !entry.is_due(unix_now()) {
+                continue;
+            }
+
+            // 別のタスクが同じエントリーを送信中の場合は二重に送信しない
+            let Some(_guard) = RunningGuard::acquire(&self.delivering_entries, &entry.id) else {
+                continue;
+            };
+
+            match self
+                .notification_service
+                .send_notifications(
+                    &entry.feed_url,
+                    &entry.id,
+                    entry.sent_messages,
+                    entry.notifications.clone(),
+                )
+                .await
+            {
+                Ok(()) => {
+                    info!(
+                        "Delivered outbox entry {} of '{}' after {} attempts",
+                        entry.id, entry.feed_name, entry.attempts
+                    );
+                    self.outbox_repository
+                        .remove(&entry.id)
+                        .map_err(|e| AppError::Outbox(e.to_string()))?;
+                    self.event_publisher.publish(RssEvent::NotificationSent {
+                        count: entry.notifications.len(),
+                    });
+                    report.delivered += 1;
+                }
+                Err(e) => {
+                    entry.record_failure(&e, &self.retry_policy, unix_now());
+                    warn!(
+                        "Failed to deliver outbox entry {} of '{}' (attempt {}), next attempt at {}: {}",
+                        entry.id, entry.feed_name, entry.attempts, entry.next_attempt_at, e
+                    );
+                    self.outbox_repository
+                        .save(&entry)
+                        .map_err(|e| AppError::Outbox(e.to_string()))?;
+                    report.failed += 1;
+                }
+            }
+        }
+
+        report.remaining = self.outbox_entries()?.len();
+        Ok(report)
+    }
+
+    /// 通知済みのアイテムを除外したチャンネルを取得する
+    ///
+    /// # Arguments
+    /// * `feed_url` - RSSフィードのURL
+    /// * `rss_channel` - RSSチャンネル
+    fn filter_unseen_items(&self, feed_url: &str, mut rss_channel: Channel) -> Result<Channel, AppError> {
+        let mut unseen_items = Vec::new();
+
+        for item in rss_channel.items.drain(..) {
+            let seen = self
+                .seen_item_repository
+                .is_seen(feed_url, &seen_key(&item))
+                .map_err(|e| AppError::SeenItemStore(e.to_string()))?;
+            if !seen {
+                unseen_items.push(item);
+            }
+        }
+
+        rss_channel.items = unseen_items;
+        Ok(rss_channel)
+    }
+
+    /// 登録されたフィードに対してユースケースを実行する
+    ///
+    /// 同じフィードの前回の実行が終わっていない場合は実行しない
+    ///
+    /// # Arguments
+    /// * `feed` - 処理対象のフィード
+    pub async fn execute_feed(&self, feed: &Feed) -> Result<ExecutionOutcome, AppError> {
+        let Some(_guard) = RunningGuard::acquire(&self.running_feeds, &feed.url) else {
+            warn!("Feed '{}' is already running, skipping", feed.name);
+            return Ok(ExecutionOutcome::AlreadyRunning);
+        };
+
+        info!("Processing feed '{}' ({})", feed.name, feed.url);
+        self.execute(feed).await
+    }
+
+    /// 登録されている全てのフィードに対してユースケースを実行する
+    ///
+    /// 1つのフィードが失敗しても残りのフィードの処理は継続する
+    ///
+    /// # Arguments
+    /// * `feeds` - 処理対象のフィードのリスト
+    pub async fn execute_all(&self, feeds: &[Feed]) -> Vec<FeedExecutionResult> {
+        let mut results = Vec::with_capacity(feeds.len());
+
+        for feed in feeds {
+            let result = self.execute_feed(feed).await;
+            if let Err(e) = &result {
+                error!("Failed to process feed '{}': {}", feed.name, e);
+            }
+            results.push(FeedExecutionResult {
+                feed: feed.clone(),
+                result,
+            });
+        }
+
+        results
+    }
+
     /// 通知データを作成し、制限する
     ///
     /// # Arguments
     /// * `summary` - RSSサマリー
+    /// * `rss_channel` - 要約の元になったRSSチャンネル
     /// * `limit` - 制限数
     fn create_notifications(
         &self,
         summary: &ArticlesResponse,
+        rss_channel: &Channel,
         limit: usize,
     ) -> Vec<Notification> {
+        let metadata_builder = &NotificationMetadataBuilder::new(rss_channel);
         let notifications = summary
             .data
             .summary
@@ -170,13 +512,10 @@ where
                                     .articles
                                     .iter()
                                     .map(|article| {
-                                        let value_string = format!(
-                                            "{}\n[この記事を読む]({})",
-                                            article.description, article.link
-                                        );
                                         NotificationField {
                                             name: article.title.clone(),
-                                            value: value_string
+                                            value: article.description.clone(),
+                                            url: Some(article.link.clone()),
                                         }
                                         // Notification {
                                         //     title: category_name.clone(),
@@ -188,9 +527,14 @@ where
                                     })
                                     .collect::<Vec<_>>();
                                 
+                                let metadata = metadata_builder.build(
+                                    category_details.articles.iter().map(|article| article.link.as_str()),
+                                );
+
                                 Notification {
                                     title: category_name.clone(),
-                                    fields: notification_fields
+                                    fields: notification_fields,
+                                    metadata,
                                 }
                             })
                     })
@@ -201,9 +545,46 @@ where
 
         // 通知データの制限
         if notifications.len() > limit {
+            let dropped_articles: usize = notifications[limit..]
+                .iter()
+                .map(|notification| notification.fields.len())
+                .sum();
+            warn!(
+                "Notifications exceed limit {}, dropping {} categories with {} articles",
+                limit,
+                notifications.len() - limit,
+                dropped_articles
+            );
             notifications[..limit].to_vec()
         } else {
             notifications
         }
     }
 }
+
+/// 通知に含まれる記事に対応するアイテムのキーを取得する
+///
+/// リンクを持たないアイテムは要約の検証で必ず除外され、通知されることがないため、
+/// 毎回要約し直さないよう通知したものとして扱う
+///
+/// # Arguments
+/// * `rss_channel` - 要約の元になったRSSチャンネル
+/// * `notifications` - 送信する通知のリスト
+fn notified_item_keys(rss_channel: &Channel, notifications: &[Notification]) -> Vec<String> {
+    let notified_links: HashSet<String> = notifications
+        .iter()
+        .flat_map(|notification| notification.fields.iter())
+        .filter_map(|field| field.url.as_deref())
+        .map(normalize_link)
+        .collect();
+
+    rss_channel
+        .items
+        .iter()
+        .filter(|item| {
+            item.link()
+                .is_none_or(|link| notified_links.contains(&normalize_link(link)))
+        })
+        .map(seen_key)
+        .collect()
+}
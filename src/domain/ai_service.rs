@@ -1,3 +1,4 @@
+use serde_json::Value;
 use std::error::Error;
 use std::fmt;
 
@@ -6,7 +7,6 @@ use std::fmt;
 pub enum AiServiceError {
     RequestError(String),
     ResponseError(String),
-    ParseError(String),
 }
 
 impl fmt::Display for AiServiceError {
@@ -14,7 +14,6 @@ impl fmt::Display for AiServiceError {
         match self {
             AiServiceError::RequestError(e) => write!(f, "AI request error: {}", e),
             AiServiceError::ResponseError(e) => write!(f, "AI response error: {}", e),
-            AiServiceError::ParseError(e) => write!(f, "AI parse error: {}", e),
         }
     }
 }
@@ -26,6 +25,8 @@ impl Error for AiServiceError {}
 pub struct AiRequest {
     pub prompt: String,
     pub context: String,
+    /// 出力をJSONに限定する場合のJSON Schema
+    pub response_schema: Option<Value>,
 }
 
 /// AIレスポンスの内容を表す構造体
@@ -41,4 +42,7 @@ pub trait AiService {
     /// # Arguments
     /// * `request` - AIへのリクエスト
     async fn process_request(&self, request: AiRequest) -> Result<AiResponse, AiServiceError>;
+
+    /// 使用しているモデルを識別する名前を取得する
+    fn model_name(&self) -> String;
 }
@@ -1,5 +1,5 @@
 use crate::domain::model::rss_data::RssData;
-use crate::domain::model::rss_summary::ArticlesResponse;
+use crate::domain::model::rss_summary::{Article, ArticlesResponse};
 use rss::Channel;
 
 /// RSSイベントを表す列挙型
@@ -8,7 +8,7 @@ pub enum RssEvent {
     /// RSSフィードが取得されたイベント
     FeedFetched {
         url: String,
-        channel: Channel,
+        channel: Box<Channel>,
     },
     /// RSSデータが変換されたイベント
     DataConverted {
@@ -18,6 +18,11 @@ pub enum RssEvent {
     SummaryGenerated {
         summary: ArticlesResponse,
     },
+    /// RSSサマリーが入力と照合して検証されたイベント
+    SummaryValidated {
+        /// 入力に存在しないリンクを持っていたため除外した記事
+        dropped_articles: Vec<Article>,
+    },
     /// 通知が送信されたイベント
     NotificationSent {
         count: usize,
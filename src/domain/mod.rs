@@ -1,4 +1,5 @@
 pub mod ai_service;
+pub mod clock;
 pub mod event;
 pub mod model;
 pub mod notification;
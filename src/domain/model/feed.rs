@@ -0,0 +1,127 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// 通知数のデフォルト値
+const DEFAULT_NOTIFICATION_LIMIT: usize = 10;
+
+/// 監視対象のフィードを表す構造体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Feed {
+    pub url: String,
+    pub name: String,
+    #[serde(default = "default_notification_limit")]
+    pub notification_limit: usize,
+    #[serde(default)]
+    pub schedule: Option<FeedSchedule>,
+    /// 要約に使用するプロンプトテンプレートのファイルパス
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    /// 要約の言語
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 要約のカテゴリ数の上限 (省略時は通知の制限数)
+    #[serde(default)]
+    pub max_categories: Option<usize>,
+    /// Telegramの通知先 (省略時は環境変数の設定を使用する)
+    #[serde(default)]
+    pub telegram: Option<TelegramTarget>,
+}
+
+/// フィードごとのTelegramの通知先を表す構造体
+///
+/// 省略した項目は環境変数の設定を使用する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramTarget {
+    #[serde(default)]
+    pub bot_token: Option<String>,
+    /// チャットID (数値または `@channelusername`)
+    #[serde(default, deserialize_with = "deserialize_chat_id")]
+    pub chat_id: Option<String>,
+}
+
+/// フィードの定期実行のスケジュールを表す構造体
+///
+/// `interval_secs` と `cron` のどちらか一方を指定する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedSchedule {
+    /// 実行間隔(秒)
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+    /// cron式(秒 分 時 日 月 曜日 [年])
+    #[serde(default)]
+    pub cron: Option<String>,
+    /// 実行タイミングをランダムに遅らせる最大秒数
+    #[serde(default)]
+    pub jitter_secs: u64,
+}
+
+fn default_notification_limit() -> usize {
+    DEFAULT_NOTIFICATION_LIMIT
+}
+
+/// チャットIDを数値と文字列のどちらでも受け付ける
+fn deserialize_chat_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ChatId {
+        Number(i64),
+        Text(String),
+    }
+
+    Ok(Option::<ChatId>::deserialize(deserializer)?.map(|chat_id| match chat_id {
+        ChatId::Number(id) => id.to_string(),
+        ChatId::Text(id) => id,
+    }))
+}
+
+impl Feed {
+    /// 新しいフィードを作成する
+    ///
+    /// # Arguments
+    /// * `url` - フィードのURL
+    /// * `name` - フィードの表示名
+    /// * `notification_limit` - 通知の制限数
+    pub fn new(url: String, name: String, notification_limit: usize) -> Self {
+        Self {
+            url,
+            name,
+            notification_limit,
+            schedule: None,
+            prompt_template: None,
+            language: None,
+            max_categories: None,
+            telegram: None,
+        }
+    }
+}
+
+/// 監視対象のフィード一覧を表す構造体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedRegistry {
+    feeds: Vec<Feed>,
+}
+
+impl FeedRegistry {
+    /// 新しいフィードレジストリを作成する
+    ///
+    /// # Arguments
+    /// * `feeds` - 登録するフィードのリスト
+    pub fn new(feeds: Vec<Feed>) -> Self {
+        Self { feeds }
+    }
+
+    /// 登録されているフィードを取得する
+    pub fn feeds(&self) -> &[Feed] {
+        &self.feeds
+    }
+}
+
+impl Default for FeedRegistry {
+    /// 設定ファイルがない場合に使用するデフォルトのフィード
+    fn default() -> Self {
+        Self::new(vec![Feed::new(
+            "https://zenn.dev/feed".to_string(),
+            "Zenn".to_string(),
+            DEFAULT_NOTIFICATION_LIMIT,
+        )])
+    }
+}
@@ -1,2 +1,4 @@
+pub mod feed;
 pub mod rss_data;
 pub mod rss_summary;
+pub mod outbox;
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::domain::model::feed::Feed;
+use crate::domain::notification::{Notification, NotificationError};
+
+/// 同じ秒に同じフィードへ送信した場合でもIDが重複しないようにするための連番
+static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// 送信できなかった通知を再送するためのエントリー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub feed_name: String,
+    pub feed_url: String,
+    pub notifications: Vec<Notification>,
+    /// 送信を試みた回数
+    pub attempts: u32,
+    /// 送信が完了したメッセージ数 (再送ではこの数だけメッセージを飛ばす)
+    #[serde(default)]
+    pub sent_messages: usize,
+    /// 作成日時 (UNIXエポックからの秒数)
+    pub created_at: u64,
+    /// 次に再送する日時 (UNIXエポックからの秒数)
+    pub next_attempt_at: u64,
+    pub last_error: Option<String>,
+}
+
+impl OutboxEntry {
+    /// 送信ごとに一意なIDを作成する
+    ///
+    /// 最初の送信の前に作成し、送信に失敗した場合はそのままエントリーのIDとして再送に使用する
+    ///
+    /// # Arguments
+    /// * `feed` - 通知の元になったフィード
+    /// * `now` - 現在時刻 (UNIXエポックからの秒数)
+    pub fn new_id(feed: &Feed, now: u64) -> String {
+        let sequence = NEXT_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        format!("{}-{:x}-{}", now, fnv1a(&feed.url), sequence)
+    }
+
+    /// 最初の送信に失敗した通知からエントリーを作成する
+    ///
+    /// # Arguments
+    /// * `id` - 最初の送信に使用したID
+    /// * `feed` - 通知の元になったフィード
+    /// * `notifications` - 送信できなかった通知のリスト
+    /// * `error` - 送信時のエラー
+    /// * `retry_policy` - 再送の間隔
+    /// * `now` - 現在時刻 (UNIXエポックからの秒数)
+    pub fn new(
+        id: String,
+        feed: &Feed,
+        notifications: Vec<Notification>,
+        error: &NotificationError,
+        retry_policy: &RetryPolicy,
+        now: u64,
+    ) -> Self {
+        Self {
+            id,
+            feed_name: feed.name.clone(),
+            feed_url: feed.url.clone(),
+            notifications,
+            attempts: 1,
+            sent_messages: error.sent_messages(),
+            created_at: now,
+            next_attempt_at: now + retry_policy.delay(1).as_secs(),
+            last_error: Some(error.to_string()),
+        }
+    }
+
+    /// 再送する時刻になっているかどうかを確認する
+    ///
+    /// # Arguments
+    /// * `now` - 現在時刻 (UNIXエポックからの秒数)
+    pub fn is_due(&self, now: u64) -> bool {
+        self.next_attempt_at <= now
+    }
+
+    /// 再送に失敗したことを記録し、次に再送する時刻を設定する
+    ///
+    /// # Arguments
+    /// * `error` - 送信時のエラー
+    /// * `retry_policy` - 再送の間隔
+    /// * `now` - 現在時刻 (UNIXエポックからの秒数)
+    pub fn record_failure(&mut self, error: &NotificationError, retry_policy: &RetryPolicy, now: u64) {
+        self.attempts += 1;
+        self.sent_messages = self.sent_messages.max(error.sent_messages());
+        self.next_attempt_at = now + retry_policy.delay(self.attempts).as_secs();
+        self.last_error = Some(error.to_string());
+    }
+}
+
+/// 再送の間隔を指数関数的に延ばすポリシー
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// 新しい再送ポリシーを作成する
+    ///
+    /// # Arguments
+    /// * `base_delay` - 1回目の失敗後の待ち時間
+    /// * `max_delay` - 待ち時間の上限
+    pub fn new(base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            base_delay,
+            max_delay,
+        }
+    }
+
+    /// 指定した回数だけ失敗した後の待ち時間を計算する
+    ///
+    /// # Arguments
+    /// * `attempts` - 失敗した回数
+    pub fn delay(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(31);
+        self.base_delay
+            .checked_mul(1 << exponent)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// エントリーのIDに使用するための文字列のハッシュ値を計算する
+///
+/// # Arguments
+/// * `value` - ハッシュ値を計算する文字列
+fn fnv1a(value: &str) -> u32 {
+    value
+        .bytes()
+        .fold(0x811c9dc5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+}
@@ -7,28 +7,3 @@ pub struct RssData {
     pub description: Option<String>,
     pub link: Option<String>,
 }
-
-impl RssData {
-    /// 新しいRSSデータを作成する
-    ///
-    /// # Arguments
-    /// * `title` - タイトル
-    /// * `description` - 説明
-    /// * `link` - リンク
-    pub fn new(
-        title: Option<String>,
-        description: Option<String>,
-        link: Option<String>,
-    ) -> Self {
-        Self {
-            title,
-            description,
-            link,
-        }
-    }
-
-    /// RSSデータが有効かどうかを確認する
-    pub fn is_valid(&self) -> bool {
-        self.title.is_some() || self.description.is_some() || self.link.is_some()
-    }
-}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 /// RSSサマリーのレスポンスを表す構造体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,12 +17,52 @@ pub struct ArticlesData {
 }
 
 /// カテゴリを表す構造体
+///
+/// `{"カテゴリ名": {...}}` の形式に加え、構造化出力で使用する
+/// `{"category": "カテゴリ名", ...}` の形式からも読み込める
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "CategoryRepr")]
 pub struct Category {
     #[serde(flatten)]
     pub category_map: HashMap<String, CategoryDetails>,
 }
 
+/// カテゴリの読み込み時の表現を表す列挙型
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CategoryRepr {
+    /// カテゴリ名をフィールドとして持つ形式
+    Named {
+        category: String,
+        #[serde(default)]
+        category_count: Option<usize>,
+        articles: Vec<Article>,
+    },
+    /// カテゴリ名をキーとして持つ形式
+    Map(HashMap<String, CategoryDetails>),
+}
+
+impl From<CategoryRepr> for Category {
+    fn from(repr: CategoryRepr) -> Self {
+        match repr {
+            CategoryRepr::Named {
+                category,
+                category_count,
+                articles,
+            } => Self {
+                category_map: HashMap::from([(
+                    category,
+                    CategoryDetails {
+                        category_count,
+                        articles,
+                    },
+                )]),
+            },
+            CategoryRepr::Map(category_map) => Self { category_map },
+        }
+    }
+}
+
 /// カテゴリの詳細を表す構造体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryDetails {
@@ -47,6 +88,48 @@ impl ArticlesResponse {
     pub fn new(message: String, data: ArticlesData) -> Self {
         Self { message, data }
     }
+
+    /// 構造化出力に使用するJSON Schemaを取得する
+    ///
+    /// JSON Schemaでは任意のキーを持つオブジェクトを表現できないため、
+    /// カテゴリはカテゴリ名を `category` フィールドに持つ形式で表現する
+    pub fn json_schema() -> Value {
+        let article = json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "description": { "type": "string" },
+                "link": { "type": "string" }
+            },
+            "required": ["title", "description", "link"]
+        });
+
+        let category = json!({
+            "type": "object",
+            "properties": {
+                "category": { "type": "string" },
+                "category_count": { "type": "integer" },
+                "articles": { "type": "array", "items": article }
+            },
+            "required": ["category", "category_count", "articles"]
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "message": { "type": "string" },
+                "data": {
+                    "type": "object",
+                    "properties": {
+                        "total": { "type": "integer" },
+                        "summary": { "type": "array", "items": category }
+                    },
+                    "required": ["total", "summary"]
+                }
+            },
+            "required": ["message", "data"]
+        })
+    }
 }
 
 impl ArticlesData {
@@ -70,18 +153,6 @@ impl ArticlesData {
     }
 }
 
-impl Category {
-    /// カテゴリ名を取得する
-    pub fn get_name(&self) -> Option<String> {
-        self.category_map.keys().next().map(|s| s.clone())
-    }
-
-    /// カテゴリの詳細を取得する
-    pub fn get_details(&self) -> Option<&CategoryDetails> {
-        self.category_map.values().next()
-    }
-}
-
 impl CategoryDetails {
     /// 記事の数を取得する
     pub fn article_count(&self) -> usize {
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 
@@ -5,12 +6,29 @@ use std::fmt;
 #[derive(Debug)]
 pub enum NotificationError {
     SendError(String),
+    /// 複数のメッセージのうち、一部を送信した後に失敗した
+    PartiallySent { sent_messages: usize, error: String },
+}
+
+impl NotificationError {
+    /// 失敗するまでに送信が完了したメッセージ数を取得する
+    pub fn sent_messages(&self) -> usize {
+        match self {
+            NotificationError::SendError(_) => 0,
+            NotificationError::PartiallySent { sent_messages, .. } => *sent_messages,
+        }
+    }
 }
 
 impl fmt::Display for NotificationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             NotificationError::SendError(e) => write!(f, "Notification send error: {}", e),
+            NotificationError::PartiallySent { sent_messages, error } => write!(
+                f,
+                "Notification send error after {} messages: {}",
+                sent_messages, error
+            ),
         }
     }
 }
@@ -18,17 +36,37 @@ impl fmt::Display for NotificationError {
 impl Error for NotificationError {}
 
 /// 通知フィールドを表す構造体
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationField {
     pub name: String,
     pub value: String,
+    /// 記事へのリンク (通知先の形式に合わせてリンクを表示する)
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// 通知の付加情報を表す構造体
+///
+/// 通知先が表示に対応していない項目は無視される
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationMetadata {
+    /// フィードのタイトル
+    pub source_name: Option<String>,
+    /// フィードのリンク
+    pub source_url: Option<String>,
+    /// サムネイル画像のURL
+    pub thumbnail_url: Option<String>,
+    /// 記事の最新の公開日時 (RFC 3339)
+    pub timestamp: Option<String>,
 }
 
 /// 通知を表す構造体
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Notification {
     pub title: String,
     pub fields: Vec<NotificationField>,
+    #[serde(default)]
+    pub metadata: NotificationMetadata,
 }
 
 /// 通知サービスのトレイト
@@ -36,6 +74,15 @@ pub trait NotificationService {
     /// 通知を送信する
     ///
     /// # Arguments
+    /// * `feed_url` - 通知の元になったフィードのURL (フィードごとに通知先を切り替える場合に使用する)
+    /// * `delivery_id` - 送信ごとに一意なID (アウトボックスからの再送では最初の送信と同じIDになる)
+    /// * `sent_messages` - 前回までに送信が完了したメッセージ数 (再送ではこの数だけメッセージを飛ばす)
     /// * `notifications` - 送信する通知のリスト
-    async fn send_notifications(&self, notifications: Vec<Notification>) -> Result<(), NotificationError>;
+    async fn send_notifications(
+        &self,
+        feed_url: &str,
+        delivery_id: &str,
+        sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError>;
 }
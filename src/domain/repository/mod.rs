@@ -1 +1,4 @@
+pub mod outbox_repository;
 pub mod rss_repository;
+pub mod seen_item_repository;
+pub mod summary_cache;
@@ -0,0 +1,41 @@
+use std::fmt;
+
+use crate::domain::model::outbox::OutboxEntry;
+
+/// アウトボックスのエラー型
+#[derive(Debug)]
+pub enum OutboxRepositoryError {
+    ReadError(String),
+    WriteError(String),
+}
+
+impl fmt::Display for OutboxRepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutboxRepositoryError::ReadError(e) => write!(f, "Failed to read outbox: {}", e),
+            OutboxRepositoryError::WriteError(e) => write!(f, "Failed to write outbox: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OutboxRepositoryError {}
+
+/// 送信できなかった通知を保存するアウトボックスのトレイト
+pub trait OutboxRepository {
+    /// エントリーを保存する
+    ///
+    /// 同じIDのエントリーがある場合は置き換える
+    ///
+    /// # Arguments
+    /// * `entry` - 保存するエントリー
+    fn save(&self, entry: &OutboxEntry) -> Result<(), OutboxRepositoryError>;
+
+    /// 全てのエントリーを作成日時の順に取得する
+    fn list(&self) -> Result<Vec<OutboxEntry>, OutboxRepositoryError>;
+
+    /// エントリーを削除する
+    ///
+    /// # Arguments
+    /// * `id` - 削除するエントリーのID
+    fn remove(&self, id: &str) -> Result<(), OutboxRepositoryError>;
+}
@@ -21,6 +21,15 @@ impl fmt::Display for RssRepositoryError {
 
 impl std::error::Error for RssRepositoryError {}
 
+/// フィード取得の結果を表す列挙型
+#[derive(Debug, Clone)]
+pub enum FeedFetchResult {
+    /// フィードを取得した
+    Fetched(Box<Channel>),
+    /// 前回の取得からフィードが更新されていない
+    NotModified,
+}
+
 /// RSSリポジトリのトレイト
 #[async_trait]
 pub trait RssRepository {
@@ -28,7 +37,16 @@ pub trait RssRepository {
     ///
     /// # Arguments
     /// * `url` - RSSフィードのURL
-    async fn fetch_feed(&self, url: &str) -> Result<Channel, RssRepositoryError>;
+    async fn fetch_feed(&self, url: &str) -> Result<FeedFetchResult, RssRepositoryError>;
+
+    /// 取得したフィードの処理が完了したことを記録する
+    ///
+    /// 処理に失敗したフィードが次回の取得で未更新として扱われないよう、
+    /// 処理が完了した時点で初めて次回の条件付き取得に使用する
+    ///
+    /// # Arguments
+    /// * `url` - RSSフィードのURL
+    fn mark_feed_processed(&self, url: &str);
 
     /// RSSデータをモデルに変換する
     fn convert_to_rss_data(&self, rss_channel: &Channel) -> Vec<RssData>;
@@ -0,0 +1,61 @@
+use rss::Item;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// 通知済みアイテムストアのエラー型
+#[derive(Debug)]
+pub enum SeenItemRepositoryError {
+    ReadError(String),
+    WriteError(String),
+}
+
+impl fmt::Display for SeenItemRepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeenItemRepositoryError::ReadError(e) => write!(f, "Failed to read seen items: {}", e),
+            SeenItemRepositoryError::WriteError(e) => write!(f, "Failed to write seen items: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SeenItemRepositoryError {}
+
+/// アイテムを識別するキーを取得する
+///
+/// guidを優先し、guidがない場合はリンクを使用する。
+/// どちらもない場合はタイトルと本文のハッシュを使用する
+///
+/// # Arguments
+/// * `item` - RSSアイテム
+pub fn seen_key(item: &Item) -> String {
+    item.guid()
+        .map(|guid| guid.value().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| item.link().map(|link| link.to_string()))
+        .unwrap_or_else(|| {
+            let mut hasher = Sha256::new();
+            for part in [item.title(), item.description(), item.content()] {
+                hasher.update(part.unwrap_or_default().as_bytes());
+                hasher.update([0]);
+            }
+            let hash = hasher.finalize();
+            format!("sha256:{}", hash.iter().map(|byte| format!("{:02x}", byte)).collect::<String>())
+        })
+}
+
+/// 通知済みアイテムを管理するリポジトリのトレイト
+pub trait SeenItemRepository {
+    /// アイテムが通知済みかどうかを確認する
+    ///
+    /// # Arguments
+    /// * `feed_url` - フィードのURL
+    /// * `key` - アイテムのキー
+    fn is_seen(&self, feed_url: &str, key: &str) -> Result<bool, SeenItemRepositoryError>;
+
+    /// アイテムを通知済みとして記録する
+    ///
+    /// # Arguments
+    /// * `feed_url` - フィードのURL
+    /// * `keys` - アイテムのキーのリスト
+    fn mark_seen(&self, feed_url: &str, keys: &[String]) -> Result<(), SeenItemRepositoryError>;
+}
@@ -0,0 +1,19 @@
+use crate::domain::model::rss_summary::ArticlesResponse;
+
+/// 生成済みのサマリーをキャッシュするトレイト
+///
+/// キャッシュの読み書きに失敗しても要約自体は継続できるため、失敗は実装側で記録する
+pub trait SummaryCache {
+    /// キャッシュからサマリーを取得する
+    ///
+    /// # Arguments
+    /// * `key` - キャッシュのキー
+    fn get(&self, key: &str) -> Option<ArticlesResponse>;
+
+    /// サマリーをキャッシュに保存する
+    ///
+    /// # Arguments
+    /// * `key` - キャッシュのキー
+    /// * `summary` - 保存するサマリー
+    fn put(&self, key: &str, summary: &ArticlesResponse);
+}
@@ -1,26 +1,30 @@
 use std::error::Error;
 use std::fmt;
 
+use crate::domain::ai_service::AiServiceError;
+
 /// RSSサマリー関連のエラー型
 #[derive(Debug)]
 pub enum RssSummaryError {
-    HttpError(String),
-    EnvVarError(String),
-    JsonError(String),
-    Base64Error(String),
-    Utf8Error(String),
-    SummaryError(String),
+    Ai(String),
+    EnvVar(String),
+    Json(String),
+    Base64(String),
+    Utf8(String),
+    Summary(String),
+    Template(String),
 }
 
 impl fmt::Display for RssSummaryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            RssSummaryError::HttpError(e) => write!(f, "HTTP error: {}", e),
-            RssSummaryError::EnvVarError(e) => write!(f, "Environment variable error: {}", e),
-            RssSummaryError::JsonError(e) => write!(f, "JSON error: {}", e),
-            RssSummaryError::Base64Error(e) => write!(f, "Base64 error: {}", e),
-            RssSummaryError::Utf8Error(e) => write!(f, "UTF-8 error: {}", e),
-            RssSummaryError::SummaryError(e) => write!(f, "Summary error: {}", e),
+            RssSummaryError::Ai(e) => write!(f, "AI error: {}", e),
+            RssSummaryError::EnvVar(e) => write!(f, "Environment variable error: {}", e),
+            RssSummaryError::Json(e) => write!(f, "JSON error: {}", e),
+            RssSummaryError::Base64(e) => write!(f, "Base64 error: {}", e),
+            RssSummaryError::Utf8(e) => write!(f, "UTF-8 error: {}", e),
+            RssSummaryError::Summary(e) => write!(f, "Summary error: {}", e),
+            RssSummaryError::Template(e) => write!(f, "Prompt template error: {}", e),
         }
     }
 }
@@ -29,30 +33,36 @@ impl Error for RssSummaryError {}
 
 impl From<std::env::VarError> for RssSummaryError {
     fn from(err: std::env::VarError) -> Self {
-        RssSummaryError::EnvVarError(err.to_string())
+        RssSummaryError::EnvVar(err.to_string())
+    }
+}
+
+impl From<AiServiceError> for RssSummaryError {
+    fn from(err: AiServiceError) -> Self {
+        RssSummaryError::Ai(err.to_string())
     }
 }
 
 impl From<serde_json::Error> for RssSummaryError {
     fn from(err: serde_json::Error) -> Self {
-        RssSummaryError::JsonError(err.to_string())
+        RssSummaryError::Json(err.to_string())
     }
 }
 
 impl From<base64::DecodeError> for RssSummaryError {
     fn from(err: base64::DecodeError) -> Self {
-        RssSummaryError::Base64Error(err.to_string())
+        RssSummaryError::Base64(err.to_string())
     }
 }
 
 impl From<std::string::FromUtf8Error> for RssSummaryError {
     fn from(err: std::string::FromUtf8Error) -> Self {
-        RssSummaryError::Utf8Error(err.to_string())
+        RssSummaryError::Utf8(err.to_string())
     }
 }
 
 impl<E: Error + 'static> From<Box<E>> for RssSummaryError {
     fn from(err: Box<E>) -> Self {
-        RssSummaryError::SummaryError(err.to_string())
+        RssSummaryError::Summary(err.to_string())
     }
 }
@@ -1,7 +1,5 @@
 pub mod error;
-pub mod model;
 pub mod service;
 
 pub use error::RssSummaryError;
-pub use model::ArticlesResponse;
 pub use service::RssSummaryService;
@@ -1,4 +1,5 @@
 use crate::domain::rss_summary::error::RssSummaryError;
+use crate::domain::model::feed::Feed;
 use crate::domain::model::rss_summary::ArticlesResponse;
 use rss::Channel;
 
@@ -8,8 +9,10 @@ pub trait RssSummaryService {
     ///
     /// # Arguments
     /// * `rss_channel` - RSSチャンネルデータ
+    /// * `feed` - 要約するフィードの設定
     async fn fetch_summary(
         &self,
         rss_channel: &Channel,
+        feed: &Feed,
     ) -> Result<ArticlesResponse, RssSummaryError>;
 }
@@ -0,0 +1,114 @@
+use log::error;
+use std::env;
+use std::str::FromStr;
+
+use crate::domain::ai_service::{AiRequest, AiResponse, AiService, AiServiceError};
+use crate::infrastructure::gemini::ai_service::GeminiAiService;
+use crate::infrastructure::http_client::HttpClient;
+use crate::infrastructure::ollama::ai_service::OllamaAiService;
+use crate::infrastructure::openai::ai_service::OpenAiCompatibleAiService;
+
+/// 設定で選択されたAIサービス
+///
+/// `AI_PROVIDER` 環境変数で使用するバックエンドを切り替える
+pub enum AiProvider<T: HttpClient> {
+    Gemini(GeminiAiService<T>),
+    OpenAiCompatible(OpenAiCompatibleAiService<T>),
+    Ollama(OllamaAiService<T>),
+}
+
+impl<T: HttpClient> AiProvider<T> {
+    /// 環境変数の設定からAIサービスを作成する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTPクライアント
+    pub fn from_env(http_client: T) -> Result<Self, String> {
+        let provider = env::var("AI_PROVIDER").unwrap_or_else(|_| "gemini".to_string());
+
+        match provider.as_str() {
+            "gemini" => {
+                let api_url = env::var("GEMINI_API_URL").unwrap_or_else(|_| {
+                    error!("GEMINI_API_URL is not set");
+                    String::new()
+                });
+                let temperature = parse_env("GEMINI_TEMPERATURE")?;
+                let max_output_tokens = parse_env("GEMINI_MAX_OUTPUT_TOKENS")?;
+                Ok(AiProvider::Gemini(GeminiAiService::new(
+                    http_client,
+                    api_url,
+                    temperature,
+                    max_output_tokens,
+                )))
+            }
+            "openai" => {
+                let base_url = env::var("OPENAI_BASE_URL")
+                    .unwrap_or_else(|_| "https://api.openai.com/v1".to_string());
+                let model = env::var("OPENAI_MODEL")
+                    .map_err(|_| "OPENAI_MODEL is not set".to_string())?;
+                let api_key = env::var("OPENAI_API_KEY").ok().filter(|key| !key.is_empty());
+                Ok(AiProvider::OpenAiCompatible(OpenAiCompatibleAiService::new(
+                    http_client,
+                    base_url,
+                    model,
+                    api_key,
+                )))
+            }
+            "ollama" => {
+                let base_url = env::var("OLLAMA_BASE_URL")
+                    .unwrap_or_else(|_| "http://localhost:11434".to_string());
+                let model = env::var("OLLAMA_MODEL")
+                    .map_err(|_| "OLLAMA_MODEL is not set".to_string())?;
+                let options = match env::var("OLLAMA_OPTIONS") {
+                    Ok(options) => Some(
+                        serde_json::from_str(&options)
+                            .map_err(|e| format!("OLLAMA_OPTIONS is not valid JSON: {}", e))?,
+                    ),
+                    Err(_) => None,
+                };
+                let json_mode = env::var("OLLAMA_JSON_MODE")
+                    .map(|value| value != "false")
+                    .unwrap_or(true);
+                Ok(AiProvider::Ollama(OllamaAiService::new(
+                    http_client,
+                    base_url,
+                    model,
+                    options,
+                    json_mode,
+                )))
+            }
+            other => Err(format!("Unknown AI_PROVIDER: {}", other)),
+        }
+    }
+}
+
+/// 環境変数を数値として取得する
+///
+/// # Arguments
+/// * `name` - 環境変数の名前
+fn parse_env<V: FromStr>(name: &str) -> Result<Option<V>, String> {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("{} is not a valid number: {}", name, value)),
+        Err(_) => Ok(None),
+    }
+}
+
+impl<T: HttpClient + Send + Sync + 'static> AiService for AiProvider<T> {
+    async fn process_request(&self, request: AiRequest) -> Result<AiResponse, AiServiceError> {
+        match self {
+            AiProvider::Gemini(service) => service.process_request(request).await,
+            AiProvider::OpenAiCompatible(service) => service.process_request(request).await,
+            AiProvider::Ollama(service) => service.process_request(request).await,
+        }
+    }
+
+    fn model_name(&self) -> String {
+        match self {
+            AiProvider::Gemini(service) => service.model_name(),
+            AiProvider::OpenAiCompatible(service) => service.model_name(),
+            AiProvider::Ollama(service) => service.model_name(),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::domain::clock::unix_now;
+use crate::domain::model::rss_summary::ArticlesResponse;
+use crate::domain::repository::summary_cache::SummaryCache;
+use crate::infrastructure::json_file::write_json_atomically;
+
+/// キャッシュのエントリーを表す構造体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// 有効期限 (UNIXエポックからの秒数)
+    expires_at: u64,
+    summary: ArticlesResponse,
+}
+
+/// TTL付きのサマリーキャッシュの実装
+///
+/// メモリ上に保持し、ファイルパスを指定した場合はファイルにも保存して再起動後も使用する
+pub struct TtlSummaryCache {
+    ttl: Duration,
+    path: Option<PathBuf>,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    /// 現在時刻 (UNIXエポックからの秒数) を取得する関数
+    clock: Box<dyn Fn() -> u64 + Send + Sync>,
+}
+
+impl TtlSummaryCache {
+    /// 新しいサマリーキャッシュを作成する
+    ///
+    /// # Arguments
+    /// * `ttl` - キャッシュの有効期間
+    /// * `path` - 保存先のファイルパス (メモリ上のみで保持する場合は `None`)
+    pub fn new(ttl: Duration, path: Option<PathBuf>) -> Self {
+        Self::with_clock(ttl, path, unix_now)
+    }
+
+    /// 現在時刻の取得方法を指定してサマリーキャッシュを作成する
+    ///
+    /// # Arguments
+    /// * `ttl` - キャッシュの有効期間
+    /// * `path` - 保存先のファイルパス (メモリ上のみで保持する場合は `None`)
+    /// * `clock` - 現在時刻 (UNIXエポックからの秒数) を取得する関数
+    pub fn with_clock(ttl: Duration, path: Option<PathBuf>, clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        let entries = path.as_ref().map(Self::load).unwrap_or_default();
+
+        Self {
+            ttl,
+            path,
+            entries: Mutex::new(entries),
+            clock: Box::new(clock),
+        }
+    }
+
+    /// ファイルからキャッシュを読み込む
+    ///
+    /// 読み込めない場合は空のキャッシュから始める
+    ///
+    /// # Arguments
+    /// * `path` - 保存先のファイルパス
+    fn load(path: &PathBuf) -> HashMap<String, CacheEntry> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring broken summary cache {}: {}", path.display(), e);
+                HashMap::new()
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                warn!("Failed to read summary cache {}: {}", path.display(), e);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// 現在の状態をファイルに書き込む
+    ///
+    /// # Arguments
+    /// * `entries` - 保存するエントリー
+    fn persist(&self, entries: &HashMap<String, CacheEntry>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        if let Err(e) = write_json_atomically(path, entries) {
+            error!("Failed to write summary cache {}: {}", path.display(), e);
+        }
+    }
+}
+
+impl SummaryCache for TtlSummaryCache {
+    fn get(&self, key: &str) -> Option<ArticlesResponse> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(key)
+            .filter(|entry| entry.expires_at > (self.clock)())
+            .map(|entry| entry.summary.clone())
+    }
+
+    fn put(&self, key: &str, summary: &ArticlesResponse) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let now = (self.clock)();
+        let mut entries = self.entries.lock().unwrap();
+
+        // 期限切れのエントリーを削除してから追加する
+        entries.retain(|_, entry| entry.expires_at > now);
+        entries.insert(
+            key.to_string(),
+            CacheEntry {
+                expires_at: now + self.ttl.as_secs(),
+                summary: summary.clone(),
+            },
+        );
+        self.persist(&entries);
+    }
+}
@@ -0,0 +1,101 @@
+use cron::Schedule;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::domain::model::feed::{FeedRegistry, FeedSchedule};
+
+/// フィード設定関連のエラー型
+#[derive(Debug)]
+pub enum FeedConfigError {
+    NotFound(String),
+    ReadError(String),
+    ParseError(String),
+    ValidationError(String),
+}
+
+impl fmt::Display for FeedConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedConfigError::NotFound(e) => write!(f, "Feed config not found: {}", e),
+            FeedConfigError::ReadError(e) => write!(f, "Failed to read feed config: {}", e),
+            FeedConfigError::ParseError(e) => write!(f, "Failed to parse feed config: {}", e),
+            FeedConfigError::ValidationError(e) => write!(f, "Invalid feed config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FeedConfigError {}
+
+/// 設定ファイルからフィードレジストリを読み込む
+///
+/// # Arguments
+/// * `path` - 設定ファイルのパス
+pub fn load_feed_registry(path: &str) -> Result<FeedRegistry, FeedConfigError> {
+    let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => FeedConfigError::NotFound(path.to_string()),
+        _ => FeedConfigError::ReadError(format!("{}: {}", path, e)),
+    })?;
+
+    let registry: FeedRegistry = serde_json::from_str(&content)
+        .map_err(|e| FeedConfigError::ParseError(format!("{}: {}", path, e)))?;
+
+    validate(&registry)?;
+
+    Ok(registry)
+}
+
+/// フィードレジストリの内容を検証する
+///
+/// # Arguments
+/// * `registry` - 検証するフィードレジストリ
+fn validate(registry: &FeedRegistry) -> Result<(), FeedConfigError> {
+    if registry.feeds().is_empty() {
+        return Err(FeedConfigError::ValidationError(
+            "at least one feed must be registered".to_string(),
+        ));
+    }
+
+    for feed in registry.feeds() {
+        if feed.url.is_empty() {
+            return Err(FeedConfigError::ValidationError(format!(
+                "feed '{}' has an empty url",
+                feed.name
+            )));
+        }
+
+        if let Some(template) = &feed.prompt_template {
+            if !Path::new(template).is_file() {
+                return Err(FeedConfigError::ValidationError(format!(
+                    "feed '{}': prompt template {} not found",
+                    feed.name, template
+                )));
+            }
+        }
+
+        if let Some(schedule) = &feed.schedule {
+            validate_schedule(schedule)
+                .map_err(|e| FeedConfigError::ValidationError(format!("feed '{}': {}", feed.name, e)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// スケジュールの内容を検証する
+///
+/// # Arguments
+/// * `schedule` - 検証するスケジュール
+fn validate_schedule(schedule: &FeedSchedule) -> Result<(), String> {
+    match (schedule.interval_secs, &schedule.cron) {
+        (Some(0), None) => Err("interval_secs must be greater than 0".to_string()),
+        (Some(_), None) => Ok(()),
+        (None, Some(expression)) => Schedule::from_str(expression)
+            .map(|_| ())
+            .map_err(|e| format!("invalid cron expression '{}': {}", expression, e)),
+        (Some(_), Some(_)) => Err("only one of interval_secs and cron can be set".to_string()),
+        (None, None) => Err("either interval_secs or cron must be set".to_string()),
+    }
+}
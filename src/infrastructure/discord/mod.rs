@@ -1,3 +1,3 @@
 pub mod notification_service;
-
-pub use notification_service::DiscordNotificationService;
+pub mod payload;
+pub mod rate_limit;
@@ -1,31 +1,17 @@
-use serde::Serialize;
+use actix_web::rt;
+use log::info;
+
 use crate::domain::notification::{Notification, NotificationError, NotificationService};
+use crate::infrastructure::discord::payload::{build_messages, DiscordMessage};
+use crate::infrastructure::discord::rate_limit::{retry_after, DiscordRateLimiter};
 use crate::infrastructure::http_client::HttpClient;
-
-/// Discord用の通知フィールドを表す構造体
-#[derive(Serialize, Debug, Clone)]
-struct DiscordEmbedField {
-    name: String,
-    value: String,
-}
-
-/// Discord用の通知を表す構造体
-#[derive(Serialize, Debug, Clone)]
-struct DiscordEmbed {
-    title: String,
-    fields: Vec<DiscordEmbedField>,
-}
-
-/// Discord用の通知データを表す構造体
-#[derive(Serialize, Debug)]
-struct DiscordEmbedData {
-    embeds: Vec<DiscordEmbed>,
-}
+use crate::infrastructure::webhook::{send_remaining, send_with_retry_after, MAX_RATE_LIMIT_WAIT};
 
 /// Discord通知サービスの実装
 pub struct DiscordNotificationService<T: HttpClient> {
     http_client: T,
     webhook_url: String,
+    rate_limiter: DiscordRateLimiter,
 }
 
 impl<T: HttpClient> DiscordNotificationService<T> {
@@ -38,55 +24,61 @@ impl<T: HttpClient> DiscordNotificationService<T> {
         Self {
             http_client,
             webhook_url,
+            rate_limiter: DiscordRateLimiter::new(),
         }
     }
 
-    /// 通知をDiscord用のデータに変換する
+    /// レート制限に従ってWebhookに送信する
+    ///
+    /// 残りの送信回数を使い切っている場合は枠がリセットされるまで待ってから送信し、
+    /// 429 Too Many Requests が返された場合は指示された時間だけ待ってから再送する
     ///
     /// # Arguments
-    /// * `notifications` - 送信する通知のリスト
-    fn to_discord_data(&self, notifications: Vec<Notification>) -> DiscordEmbedData {
-        let embeds: Vec<DiscordEmbed> = notifications
-            .into_iter()
-            .map(|notification| {
-                let fields = notification
-                    .fields
-                    .into_iter()
-                    .map(|field| DiscordEmbedField {
-                        name: field.name,
-                        value: field.value,
-                    })
-                    .collect();
-
-                DiscordEmbed {
-                    title: notification.title,
-                    fields,
-                }
-            })
-            .collect();
+    /// * `message` - 送信するメッセージ
+    async fn post_webhook(&self, message: &DiscordMessage) -> Result<(), NotificationError> {
+        let _permit = self.rate_limiter.acquire(&self.webhook_url).await;
 
-        // Discordの制限に合わせて10個までに制限
-        let limited_embeds = if embeds.len() > 10 {
-            embeds[..10].to_vec()
-        } else {
-            embeds
-        };
+        send_with_retry_after("Discord webhook", retry_after, || async move {
+            let wait = self.rate_limiter.wait_time(&self.webhook_url);
+            if wait > MAX_RATE_LIMIT_WAIT {
+                return Err(NotificationError::SendError(format!(
+                    "Rate limited for {} seconds",
+                    wait.as_secs()
+                )));
+            }
+            if !wait.is_zero() {
+                info!("Waiting {} ms for Discord rate limit", wait.as_millis());
+                rt::time::sleep(wait).await;
+            }
 
-        DiscordEmbedData {
-            embeds: limited_embeds,
-        }
+            let response = self
+                .http_client
+                .post_with_status(&self.webhook_url, message)
+                .await
+                .map_err(|e| NotificationError::SendError(e.to_string()))?;
+            self.rate_limiter.update(&self.webhook_url, &response);
+            Ok(response)
+        })
+        .await
+        .map(|_| ())
     }
 }
 
 impl<T: HttpClient + Send + Sync + 'static> NotificationService for DiscordNotificationService<T> {
-    async fn send_notifications(&self, notifications: Vec<Notification>) -> Result<(), NotificationError> {
-        // 通知をDiscord用のデータに変換
-        let discord_data = self.to_discord_data(notifications);
+    async fn send_notifications(
+        &self,
+        _feed_url: &str,
+        _delivery_id: &str,
+        sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError> {
+        // 通知をDiscordの制限に収まるメッセージに分割
+        let messages = build_messages(notifications);
+        if messages.len() > 1 {
+            info!("Splitting notifications into {} Discord messages", messages.len());
+        }
 
-        // Discordに送信
-        self.http_client
-            .post(&self.webhook_url, &discord_data)
-            .await
-            .map_err(|e| NotificationError::SendError(e.to_string()))
+        // Discordに順番に送信
+        send_remaining("Discord", &messages, sent_messages, |_, message| self.post_webhook(message)).await
     }
 }
@@ -0,0 +1,353 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::notification::{Notification, NotificationField, NotificationMetadata};
+use crate::infrastructure::text::{truncate, CONTINUATION_SUFFIX};
+
+/// 1つのメッセージに含められるEmbedの最大数
+const MAX_EMBEDS_PER_MESSAGE: usize = 10;
+/// Embedのタイトルの最大文字数
+const MAX_TITLE_LENGTH: usize = 256;
+/// Embedの著者名の最大文字数
+const MAX_AUTHOR_NAME_LENGTH: usize = 256;
+/// 1つのEmbedに含められるフィールドの最大数
+const MAX_FIELDS_PER_EMBED: usize = 25;
+/// フィールド名の最大文字数
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+/// フィールドの値の最大文字数
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+/// 1つのメッセージに含まれるEmbedの合計文字数の上限
+const MAX_TOTAL_LENGTH: usize = 6000;
+
+/// Discord用の通知フィールドを表す構造体
+#[derive(Serialize, Debug, Clone)]
+pub struct DiscordEmbedField {
+    name: String,
+    value: String,
+}
+
+impl DiscordEmbedField {
+    /// Discordの制限で数えられる文字数を取得する
+    fn char_count(&self) -> usize {
+        self.name.chars().count() + self.value.chars().count()
+    }
+}
+
+/// Embedの著者欄を表す構造体
+#[derive(Serialize, Debug, Clone)]
+pub struct DiscordEmbedAuthor {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+/// Embedのサムネイルを表す構造体
+#[derive(Serialize, Debug, Clone)]
+pub struct DiscordEmbedThumbnail {
+    url: String,
+}
+
+/// Embedのフッターを表す構造体
+#[derive(Serialize, Debug, Clone)]
+pub struct DiscordEmbedFooter {
+    text: String,
+}
+
+/// Discord用の通知を表す構造体
+#[derive(Serialize, Debug, Clone)]
+pub struct DiscordEmbed {
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    color: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<DiscordEmbedAuthor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail: Option<DiscordEmbedThumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    footer: DiscordEmbedFooter,
+    fields: Vec<DiscordEmbedField>,
+}
+
+impl DiscordEmbed {
+    /// Discordの制限で数えられる文字数を取得する
+    fn char_count(&self) -> usize {
+        self.title.chars().count()
+            + self.author.as_ref().map_or(0, |author| author.name.chars().count())
+            + self.footer.text.chars().count()
+            + self.fields.iter().map(DiscordEmbedField::char_count).sum::<usize>()
+    }
+}
+
+/// Discordに送信する1つのメッセージを表す構造体
+#[derive(Serialize, Debug, Default)]
+pub struct DiscordMessage {
+    embeds: Vec<DiscordEmbed>,
+}
+
+impl DiscordMessage {
+    /// Discordの制限で数えられる文字数を取得する
+    fn char_count(&self) -> usize {
+        self.embeds.iter().map(DiscordEmbed::char_count).sum()
+    }
+}
+
+/// 通知をDiscordの制限に収まるメッセージのリストに変換する
+///
+/// 制限を超える場合は記事を捨てずに複数のEmbed・メッセージに分割し、
+/// 長すぎる文字列のみ省略記号を付けて切り詰める
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn build_messages(notifications: Vec<Notification>) -> Vec<DiscordMessage> {
+    let mut messages = Vec::new();
+    let mut current = DiscordMessage::default();
+
+    for embed in notifications.into_iter().flat_map(build_embeds) {
+        if !current.embeds.is_empty()
+            && (current.embeds.len() >= MAX_EMBEDS_PER_MESSAGE
+                || current.char_count() + embed.char_count() > MAX_TOTAL_LENGTH)
+        {
+            messages.push(std::mem::take(&mut current));
+        }
+        current.embeds.push(embed);
+    }
+
+    if !current.embeds.is_empty() {
+        messages.push(current);
+    }
+
+    messages
+}
+
+/// 1つの通知をEmbedのリストに変換する
+///
+/// フィールド数か文字数の制限を超える場合は、同じタイトルに接尾辞を付けたEmbedに続きを入れる
+///
+/// # Arguments
+/// * `notification` - 変換する通知
+fn build_embeds(notification: Notification) -> Vec<DiscordEmbed> {
+    let title = truncate(&notification.title, MAX_TITLE_LENGTH, "Discord embed title");
+    let continuation_title = format!(
+        "{}{}",
+        truncate(
+            &notification.title,
+            MAX_TITLE_LENGTH - CONTINUATION_SUFFIX.chars().count(),
+            "Discord embed title"
+        ),
+        CONTINUATION_SUFFIX
+    );
+
+    let metadata = &notification.metadata;
+    let source_url = metadata.source_url.clone().filter(|url| is_http_url(url));
+    let base = DiscordEmbed {
+        title,
+        url: source_url.clone(),
+        color: category_color(&notification.title),
+        author: metadata.source_name.as_ref().map(|name| DiscordEmbedAuthor {
+            name: truncate(name, MAX_AUTHOR_NAME_LENGTH, "Discord author name"),
+            url: source_url,
+        }),
+        thumbnail: thumbnail(metadata),
+        timestamp: metadata.timestamp.clone(),
+        footer: DiscordEmbedFooter {
+            text: format!("{}件の記事", notification.fields.len()),
+        },
+        fields: Vec::new(),
+    };
+
+    let mut embeds = Vec::new();
+    let mut current = base.clone();
+
+    for field in notification.fields.iter().map(build_field) {
+        if !current.fields.is_empty()
+            && (current.fields.len() >= MAX_FIELDS_PER_EMBED
+                || current.char_count() + field.char_count() > MAX_TOTAL_LENGTH)
+        {
+            let next = DiscordEmbed {
+                title: continuation_title.clone(),
+                ..base.clone()
+            };
+            embeds.push(std::mem::replace(&mut current, next));
+        }
+        current.fields.push(field);
+    }
+
+    embeds.push(current);
+    embeds
+}
+
+/// カテゴリ名から常に同じになるEmbedの色を計算する
+///
+/// # Arguments
+/// * `category_name` - カテゴリ名
+fn category_color(category_name: &str) -> u32 {
+    let hash = Sha256::digest(category_name.as_bytes());
+    u32::from_be_bytes([0, hash[0], hash[1], hash[2]])
+}
+
+/// 付加情報からサムネイルを作成する
+///
+/// # Arguments
+/// * `metadata` - 通知の付加情報
+fn thumbnail(metadata: &NotificationMetadata) -> Option<DiscordEmbedThumbnail> {
+    metadata
+        .thumbnail_url
+        .as_ref()
+        .filter(|url| is_http_url(url))
+        .map(|url| DiscordEmbedThumbnail { url: url.clone() })
+}
+
+/// DiscordがEmbedのURLとして受け付ける形式かどうかを確認する
+///
+/// # Arguments
+/// * `url` - 対象のURL
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// 通知フィールドをEmbedのフィールドに変換する
+///
+/// 記事のリンクが切れないよう、長すぎる場合は説明のみを切り詰める
+///
+/// # Arguments
+/// * `field` - 変換する通知フィールド
+fn build_field(field: &NotificationField) -> DiscordEmbedField {
+    let name = truncate(&field.name, MAX_FIELD_NAME_LENGTH, "Discord field name");
+
+    let value = match &field.url {
+        Some(url) => {
+            let link = format!("\n[この記事を読む]({})", url);
+            let budget = MAX_FIELD_VALUE_LENGTH.saturating_sub(link.chars().count());
+            let value = format!("{}{}", truncate(&field.value, budget, "Discord field value"), link);
+            truncate(&value, MAX_FIELD_VALUE_LENGTH, "Discord field value")
+        }
+        None => truncate(&field.value, MAX_FIELD_VALUE_LENGTH, "Discord field value"),
+    };
+
+    // Discordは空のフィールドを受け付けないため、空の場合は記号で埋める
+    DiscordEmbedField {
+        name: non_empty(name),
+        value: non_empty(value),
+    }
+}
+
+/// 空の文字列を表示可能な記号に置き換える
+///
+/// # Arguments
+/// * `text` - 対象の文字列
+fn non_empty(text: String) -> String {
+    if text.trim().is_empty() {
+        "-".to_string()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, value: &str) -> NotificationField {
+        NotificationField {
+            name: name.to_string(),
+            value: value.to_string(),
+            url: Some(format!("https://example.com/{}", name)),
+        }
+    }
+
+    fn notification(title: &str, fields: Vec<NotificationField>) -> Notification {
+        Notification {
+            title: title.to_string(),
+            fields,
+            metadata: NotificationMetadata {
+                source_name: Some("Example".to_string()),
+                ..NotificationMetadata::default()
+            },
+        }
+    }
+
+    fn assert_within_limits(messages: &[DiscordMessage]) {
+        for message in messages {
+            assert!(message.embeds.len() <= MAX_EMBEDS_PER_MESSAGE);
+            assert!(message.char_count() <= MAX_TOTAL_LENGTH);
+            for embed in &message.embeds {
+                assert!(embed.title.chars().count() <= MAX_TITLE_LENGTH);
+                assert!(embed.fields.len() <= MAX_FIELDS_PER_EMBED);
+                for field in &embed.fields {
+                    assert!(field.name.chars().count() <= MAX_FIELD_NAME_LENGTH);
+                    assert!(field.value.chars().count() <= MAX_FIELD_VALUE_LENGTH);
+                }
+            }
+        }
+    }
+
+    fn field_names(messages: &[DiscordMessage]) -> Vec<String> {
+        messages
+            .iter()
+            .flat_map(|message| &message.embeds)
+            .flat_map(|embed| &embed.fields)
+            .map(|field| field.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn splits_fields_and_embeds_without_losing_articles() {
+        let fields: Vec<NotificationField> = (0..60)
+            .map(|i| field(&format!("article-{:02}", i), &"a".repeat(900)))
+            .collect();
+        let expected: Vec<String> = fields.iter().map(|field| field.name.clone()).collect();
+
+        let messages = build_messages(vec![notification("Rust", fields)]);
+
+        assert!(messages.len() > 1);
+        assert_within_limits(&messages);
+        assert_eq!(field_names(&messages), expected);
+        let embeds: Vec<&DiscordEmbed> = messages.iter().flat_map(|message| &message.embeds).collect();
+        assert_eq!(embeds[0].title, "Rust");
+        assert!(embeds[1..].iter().all(|embed| embed.title == format!("Rust{}", CONTINUATION_SUFFIX)));
+    }
+
+    #[test]
+    fn splits_more_than_ten_embeds_into_several_messages() {
+        let notifications: Vec<Notification> = (0..25)
+            .map(|i| notification(&format!("category-{}", i), vec![field(&format!("article-{:02}", i), "text")]))
+            .collect();
+
+        let messages = build_messages(notifications);
+
+        assert_eq!(messages.len(), 3);
+        assert_within_limits(&messages);
+        assert_eq!(field_names(&messages).len(), 25);
+    }
+
+    #[test]
+    fn truncates_long_text_but_keeps_the_article_link() {
+        let long_name = "n".repeat(300);
+        let fields = vec![field(&long_name, &"v".repeat(2000))];
+
+        let messages = build_messages(vec![notification(&"t".repeat(300), fields)]);
+
+        assert_within_limits(&messages);
+        let embed = &messages[0].embeds[0];
+        assert!(embed.title.ends_with(crate::infrastructure::text::ELLIPSIS));
+        assert!(embed.fields[0]
+            .value
+            .ends_with(&format!("[この記事を読む](https://example.com/{})", long_name)));
+    }
+
+    #[test]
+    fn fills_empty_fields_with_a_placeholder() {
+        let fields = vec![NotificationField {
+            name: " ".to_string(),
+            value: String::new(),
+            url: None,
+        }];
+
+        let messages = build_messages(vec![notification("Rust", fields)]);
+
+        assert_eq!(messages[0].embeds[0].fields[0].name, "-");
+        assert_eq!(messages[0].embeds[0].fields[0].value, "-");
+    }
+}
@@ -0,0 +1,159 @@
+use futures::lock::{Mutex as AsyncMutex, OwnedMutexGuard};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::http_client::HttpStatusResponse;
+
+/// 429 Too Many Requests のレスポンスボディ
+#[derive(Debug, Deserialize)]
+struct RateLimitedBody {
+    /// 再送までの待ち時間 (秒)
+    retry_after: f64,
+    #[serde(default)]
+    global: bool,
+}
+
+/// Webhookごとのレート制限の状態を管理する構造体
+///
+/// 同じWebhookに送信する全てのフィードで共有し、他のフィードの送信で使い切った枠を考慮して待機する
+pub struct DiscordRateLimiter {
+    /// Webhookごとの送信を再開できる時刻
+    buckets: Mutex<HashMap<String, Instant>>,
+    /// 全てのWebhookで送信を再開できる時刻 (グローバルなレート制限)
+    global_until: Mutex<Option<Instant>>,
+    /// Webhookごとの送信を1件ずつに制限するロック
+    send_locks: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+}
+
+impl DiscordRateLimiter {
+    /// 新しいレートリミッターを作成する
+    pub fn new() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            global_until: Mutex::new(None),
+            send_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Webhookへの送信を開始する
+    ///
+    /// 待ち時間の確認から `update` による状態の反映までの間に同じWebhookへの他の送信が割り込むと、
+    /// 残り1回の枠に複数の送信が同時に送られてしまうため、返されたガードを保持している間は他の送信を待たせる
+    ///
+    /// # Arguments
+    /// * `webhook_url` - 送信先のWebhook URL
+    pub async fn acquire(&self, webhook_url: &str) -> OwnedMutexGuard<()> {
+        let lock = self
+            .send_locks
+            .lock()
+            .unwrap()
+            .entry(webhook_url.to_string())
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    /// 送信できるようになるまでの待ち時間を取得する
+    ///
+    /// # Arguments
+    /// * `webhook_url` - 送信先のWebhook URL
+    pub fn wait_time(&self, webhook_url: &str) -> Duration {
+        let now = Instant::now();
+        let bucket_until = self.buckets.lock().unwrap().get(webhook_url).copied();
+        let global_until = *self.global_until.lock().unwrap();
+
+        bucket_until
+            .into_iter()
+            .chain(global_until)
+            .map(|until| until.saturating_duration_since(now))
+            .max()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// レスポンスのヘッダーとボディからレート制限の状態を更新する
+    ///
+    /// # Arguments
+    /// * `webhook_url` - 送信先のWebhook URL
+    /// * `response` - Webhookのレスポンス
+    pub fn update(&self, webhook_url: &str, response: &HttpStatusResponse) {
+        let now = Instant::now();
+
+        if let Some(retry_after) = retry_after(response) {
+            let until = now + retry_after;
+            if is_global(response) {
+                *self.global_until.lock().unwrap() = Some(until);
+            }
+            self.buckets.lock().unwrap().insert(webhook_url.to_string(), until);
+            return;
+        }
+
+        // 残りの送信回数を使い切った場合は、枠がリセットされるまで待つ
+        let remaining = response
+            .header("X-RateLimit-Remaining")
+            .and_then(|value| value.parse::<u32>().ok());
+        let reset_after = response
+            .header("X-RateLimit-Reset-After")
+            .and_then(parse_seconds);
+
+        let mut buckets = self.buckets.lock().unwrap();
+        match (remaining, reset_after) {
+            (Some(0), Some(reset_after)) => {
+                buckets.insert(webhook_url.to_string(), now + reset_after);
+            }
+            _ => {
+                buckets.remove(webhook_url);
+            }
+        }
+    }
+}
+
+impl Default for DiscordRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 429 Too Many Requests のレスポンスから再送までの待ち時間を取得する
+///
+/// ボディの `retry_after` を優先し、読み取れない場合は Retry-After ヘッダーを使用する
+///
+/// # Arguments
+/// * `response` - Webhookのレスポンス
+pub fn retry_after(response: &HttpStatusResponse) -> Option<Duration> {
+    if response.status != 429 {
+        return None;
+    }
+
+    serde_json::from_str::<RateLimitedBody>(&response.body)
+        .ok()
+        .and_then(|body| Duration::try_from_secs_f64(body.retry_after).ok())
+        .or_else(|| response.header("Retry-After").and_then(parse_seconds))
+        .or_else(|| response.header("X-RateLimit-Reset-After").and_then(parse_seconds))
+        // 待ち時間が分からない場合でも、すぐに再送しないよう1秒待つ
+        .or(Some(Duration::from_secs(1)))
+}
+
+/// グローバルなレート制限かどうかを確認する
+///
+/// # Arguments
+/// * `response` - Webhookのレスポンス
+fn is_global(response: &HttpStatusResponse) -> bool {
+    response
+        .header("X-RateLimit-Global")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+        || serde_json::from_str::<RateLimitedBody>(&response.body).is_ok_and(|body| body.global)
+}
+
+/// 小数を含む秒数の文字列を待ち時間に変換する
+///
+/// # Arguments
+/// * `value` - 秒数の文字列
+fn parse_seconds(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
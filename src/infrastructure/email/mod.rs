@@ -0,0 +1,2 @@
+pub mod notification_service;
+pub mod render;
@@ -0,0 +1,141 @@
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use log::info;
+use std::str::FromStr;
+
+use crate::domain::notification::{Notification, NotificationError, NotificationService};
+use crate::infrastructure::email::render::{render_html, render_plain_text, render_subject};
+
+/// SMTPサーバーとの接続の暗号化方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// 平文で接続した後にSTARTTLSで暗号化する (既定のポート: 587)
+    StartTls,
+    /// 最初からTLSで接続する (既定のポート: 465)
+    Tls,
+    /// 暗号化しない (ローカルのSMTPサーバーでの確認用、既定のポート: 25)
+    None,
+}
+
+impl FromStr for SmtpSecurity {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "starttls" => Ok(SmtpSecurity::StartTls),
+            "tls" => Ok(SmtpSecurity::Tls),
+            "none" => Ok(SmtpSecurity::None),
+            other => Err(format!("Unknown SMTP security: {}", other)),
+        }
+    }
+}
+
+/// SMTPの接続設定
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// 指定しない場合は暗号化方式の既定のポートを使用する
+    pub port: Option<u16>,
+    pub security: SmtpSecurity,
+    /// ユーザー名とパスワード
+    pub credentials: Option<(String, String)>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+/// メール (SMTP) 通知サービスの実装
+pub struct EmailNotificationService {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailNotificationService {
+    /// 新しいメール通知サービスを作成する
+    ///
+    /// # Arguments
+    /// * `config` - SMTPの接続設定
+    pub fn new(config: SmtpConfig) -> Result<Self, String> {
+        let mut builder = match config.security {
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| format!("Invalid SMTP host {}: {}", config.host, e))?,
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| format!("Invalid SMTP host {}: {}", config.host, e))?,
+            SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        };
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let Some((username, password)) = config.credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        let from = parse_mailbox(&config.from)?;
+        let to = config
+            .to
+            .iter()
+            .map(|address| parse_mailbox(address))
+            .collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err("No email recipients are configured".to_string());
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+
+    /// 通知からHTMLとテキストを持つメールを作成する
+    ///
+    /// # Arguments
+    /// * `notifications` - 送信する通知のリスト
+    fn create_message(&self, notifications: &[Notification]) -> Result<Message, NotificationError> {
+        let builder = self
+            .to
+            .iter()
+            .cloned()
+            .fold(Message::builder().from(self.from.clone()), |builder, to| builder.to(to));
+
+        builder
+            .subject(render_subject(notifications))
+            .multipart(MultiPart::alternative_plain_html(
+                render_plain_text(notifications),
+                render_html(notifications),
+            ))
+            .map_err(|e| NotificationError::SendError(e.to_string()))
+    }
+}
+
+/// メールアドレスを解析する
+///
+/// # Arguments
+/// * `address` - `Name <user@example.com>` または `user@example.com` 形式のアドレス
+fn parse_mailbox(address: &str) -> Result<Mailbox, String> {
+    address
+        .trim()
+        .parse()
+        .map_err(|e| format!("Invalid email address {}: {}", address, e))
+}
+
+impl NotificationService for EmailNotificationService {
+    async fn send_notifications(
+        &self,
+        _feed_url: &str,
+        _delivery_id: &str,
+        _sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError> {
+        let message = self.create_message(&notifications)?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| NotificationError::SendError(e.to_string()))?;
+
+        info!("Sent email digest to {} recipients", self.to.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::domain::notification::Notification;
+use crate::infrastructure::html::{escape_html, is_safe_link};
+
+/// 件名にフィード名がない場合に使用する件名
+const DEFAULT_SUBJECT: &str = "RSSの新着記事";
+
+/// メールの件名を作成する
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn render_subject(notifications: &[Notification]) -> String {
+    let article_count: usize = notifications.iter().map(|notification| notification.fields.len()).sum();
+    let source_name = notifications
+        .iter()
+        .find_map(|notification| notification.metadata.source_name.as_deref());
+
+    match source_name {
+        Some(source_name) => format!("{} の新着記事 ({}件)", source_name, article_count),
+        None => format!("{} ({}件)", DEFAULT_SUBJECT, article_count),
+    }
+}
+
+/// 通知をカテゴリごとの表を持つHTMLに変換する
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn render_html(notifications: &[Notification]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n<body style=\"font-family: sans-serif;\">\n",
+    );
+
+    for notification in notifications {
+        html.push_str(&format!("<h2>{}</h2>\n", escape_html(&notification.title)));
+        if let Some(source_name) = &notification.metadata.source_name {
+            let source = match notification.metadata.source_url.as_deref().filter(|url| is_safe_link(url)) {
+                Some(url) => format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(source_name)),
+                None => escape_html(source_name),
+            };
+            html.push_str(&format!(
+                "<p style=\"color: #666;\">{} ・ {}件の記事</p>\n",
+                source,
+                notification.fields.len()
+            ));
+        }
+
+        html.push_str("<table style=\"border-collapse: collapse; width: 100%;\">\n");
+        html.push_str("<tr><th style=\"text-align: left; border-bottom: 1px solid #ccc; padding: 4px;\">記事</th><th style=\"text-align: left; border-bottom: 1px solid #ccc; padding: 4px;\">概要</th></tr>\n");
+        for field in &notification.fields {
+            // http(s) 以外のリンクはテキストとして表示する
+            let title = match field.url.as_deref().filter(|url| is_safe_link(url)) {
+                Some(url) => format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(&field.name)),
+                None => escape_html(&field.name),
+            };
+            html.push_str(&format!(
+                "<tr><td style=\"vertical-align: top; border-bottom: 1px solid #eee; padding: 4px;\">{}</td><td style=\"vertical-align: top; border-bottom: 1px solid #eee; padding: 4px;\">{}</td></tr>\n",
+                title,
+                escape_html(&field.value).replace('\n', "<br>")
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 通知をHTMLを表示できないメールクライアント向けのテキストに変換する
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn render_plain_text(notifications: &[Notification]) -> String {
+    let mut text = String::new();
+
+    for notification in notifications {
+        text.push_str(&format!("■ {}\n", notification.title));
+        if let Some(source_name) = &notification.metadata.source_name {
+            text.push_str(&format!("{} ・ {}件の記事\n", source_name, notification.fields.len()));
+        }
+        text.push('\n');
+
+        for field in &notification.fields {
+            text.push_str(&format!("- {}\n", field.name));
+            if !field.value.trim().is_empty() {
+                text.push_str(&format!("  {}\n", field.value.replace('\n', "\n  ")));
+            }
+            if let Some(url) = &field.url {
+                text.push_str(&format!("  {}\n", url));
+            }
+        }
+        text.push('\n');
+    }
+
+    text
+}
@@ -1,4 +1,4 @@
-use log::info;
+use log::{info, warn};
 use std::sync::{Arc, Mutex};
 
 use crate::domain::event::rss_events::{EventPublisher, EventSubscriber, RssEvent};
@@ -51,6 +51,12 @@ impl EventPublisher for InMemoryEventPublisher {
                     summary.data.total
                 );
             }
+            RssEvent::SummaryValidated { dropped_articles } => {
+                info!(
+                    "Event: SummaryValidated with {} dropped articles",
+                    dropped_articles.len()
+                );
+            }
             RssEvent::NotificationSent { count } => {
                 info!("Event: NotificationSent with {} notifications", count);
             }
@@ -90,6 +96,21 @@ impl EventSubscriber for LoggingEventSubscriber {
                     summary.data.category_count()
                 );
             }
+            RssEvent::SummaryValidated { dropped_articles } => {
+                if dropped_articles.is_empty() {
+                    info!("LoggingEventSubscriber: RSS summary validated, no articles dropped");
+                } else {
+                    warn!(
+                        "LoggingEventSubscriber: RSS summary validated, dropped {} articles not in the feed: {}",
+                        dropped_articles.len(),
+                        dropped_articles
+                            .iter()
+                            .map(|article| article.link.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+            }
             RssEvent::NotificationSent { count } => {
                 info!(
                     "LoggingEventSubscriber: {} notifications sent",
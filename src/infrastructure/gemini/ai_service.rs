@@ -1,6 +1,6 @@
 use crate::domain::ai_service::{AiRequest, AiResponse, AiService, AiServiceError};
-use crate::infrastructure::gemini::request::GeminiRequest;
-use crate::infrastructure::gemini::response::GeminiResponse;
+use crate::infrastructure::gemini::request::GenerationConfig;
+use crate::infrastructure::gemini::{GeminiRequest, GeminiResponse};
 use crate::infrastructure::http_client::HttpClient;
 use serde_json::Value;
 
@@ -8,6 +8,8 @@ use serde_json::Value;
 pub struct GeminiAiService<T: HttpClient> {
     http_client: T,
     api_url: String,
+    temperature: Option<f32>,
+    max_output_tokens: Option<u32>,
 }
 
 impl<T: HttpClient> GeminiAiService<T> {
@@ -16,10 +18,40 @@ impl<T: HttpClient> GeminiAiService<T> {
     /// # Arguments
     /// * `http_client` - HTTP クライアント
     /// * `api_url` - Gemini API の URL
-    pub fn new(http_client: T, api_url: String) -> Self {
+    /// * `temperature` - 生成時の temperature
+    /// * `max_output_tokens` - 生成する最大トークン数
+    pub fn new(
+        http_client: T,
+        api_url: String,
+        temperature: Option<f32>,
+        max_output_tokens: Option<u32>,
+    ) -> Self {
         Self {
             http_client,
             api_url,
+            temperature,
+            max_output_tokens,
+        }
+    }
+
+    /// リクエストに応じた生成設定を作成する
+    ///
+    /// JSON Schemaが指定されている場合は構造化出力を使用し、スキーマに沿ったJSONを返させる
+    ///
+    /// # Arguments
+    /// * `response_schema` - 出力のJSON Schema
+    fn create_generation_config(&self, response_schema: Option<Value>) -> Option<GenerationConfig> {
+        let config = GenerationConfig {
+            response_mime_type: response_schema.as_ref().map(|_| "application/json".to_string()),
+            response_schema: response_schema.map(to_gemini_schema),
+            temperature: self.temperature,
+            max_output_tokens: self.max_output_tokens,
+        };
+
+        if config.is_empty() {
+            None
+        } else {
+            Some(config)
         }
     }
 
@@ -46,26 +78,18 @@ impl<T: HttpClient> GeminiAiService<T> {
             AiServiceError::ResponseError("Failed to extract text from response".to_string())
         })
     }
-
-    /// JSON レスポンスを解析する
-    ///
-    /// # Arguments
-    /// * `text` - JSON 形式のテキスト
-    fn parse_json_response(&self, text: &str) -> Result<Value, AiServiceError> {
-        // code block を削除
-        let cleaned_text = text.replace("```json", "").replace("```", "");
-        
-        serde_json::from_str(&cleaned_text).map_err(|e| {
-            AiServiceError::ParseError(format!("Failed to parse JSON response: {}", e))
-        })
-    }
 }
 
 impl<T: HttpClient + Send + Sync + 'static> AiService for GeminiAiService<T> {
     async fn process_request(&self, request: AiRequest) -> Result<AiResponse, AiServiceError> {
+        if self.api_url.is_empty() {
+            return Err(AiServiceError::RequestError("GEMINI_API_URL is empty".to_string()));
+        }
+
         // Gemini リクエストの作成
         let prompt_with_context = format!("{}\n{}", request.prompt, request.context);
-        let gemini_request = GeminiRequest::new(prompt_with_context);
+        let mut gemini_request = GeminiRequest::new(prompt_with_context);
+        gemini_request.generation_config = self.create_generation_config(request.response_schema);
 
         // Gemini API へのリクエスト
         let response: GeminiResponse = self
@@ -79,4 +103,35 @@ impl<T: HttpClient + Send + Sync + 'static> AiService for GeminiAiService<T> {
 
         Ok(AiResponse { content })
     }
+
+    fn model_name(&self) -> String {
+        // URLにはAPIキーが含まれるため、`models/{model}:generateContent` のモデル名だけを取り出す
+        self.api_url
+            .split("models/")
+            .nth(1)
+            .and_then(|rest| rest.split([':', '?', '/']).next())
+            .map(|model| format!("gemini/{}", model))
+            .unwrap_or_else(|| "gemini".to_string())
+    }
+}
+
+/// JSON SchemaをGemini APIのスキーマ形式に変換する
+///
+/// Gemini APIは型名を大文字の列挙値 (OBJECT、STRING など) で受け付けるため、`type` を大文字に変換する
+///
+/// # Arguments
+/// * `schema` - JSON Schema
+fn to_gemini_schema(schema: Value) -> Value {
+    match schema {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| match (key.as_str(), value) {
+                    ("type", Value::String(type_name)) => (key, Value::String(type_name.to_uppercase())),
+                    (_, value) => (key, to_gemini_schema(value)),
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(to_gemini_schema).collect()),
+        other => other,
+    }
 }
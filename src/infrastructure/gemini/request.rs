@@ -1,4 +1,5 @@
 use serde::Serialize;
+use serde_json::Value;
 
 /// Gemini APIのリクエスト部分を表す構造体
 #[derive(Serialize, Debug)]
@@ -12,10 +13,37 @@ pub struct Content {
     pub parts: Vec<Part>
 }
 
+/// Gemini APIの生成設定を表す構造体
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+}
+
+impl GenerationConfig {
+    /// 設定項目が1つも指定されていないかどうかを確認する
+    pub fn is_empty(&self) -> bool {
+        self.response_mime_type.is_none()
+            && self.response_schema.is_none()
+            && self.temperature.is_none()
+            && self.max_output_tokens.is_none()
+    }
+}
+
 /// Gemini APIのリクエストを表す構造体
 #[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct GeminiRequest {
-    pub contents: Vec<Content>
+    pub contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GenerationConfig>,
 }
 
 impl GeminiRequest {
@@ -28,6 +56,7 @@ impl GeminiRequest {
             contents: vec![Content {
                 parts: vec![Part { text }],
             }],
+            generation_config: None,
         }
     }
 }
@@ -0,0 +1,30 @@
+use reqwest::Url;
+
+/// HTMLの特殊文字をエスケープする
+///
+/// # Arguments
+/// * `text` - 対象の文字列
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// リンクとして埋め込んでよいURLかどうかを確認する
+///
+/// フィードの内容に `javascript:` などのURLが含まれていても実行されないよう、http と https のみを許可する
+///
+/// # Arguments
+/// * `url` - 対象のURL
+pub fn is_safe_link(url: &str) -> bool {
+    Url::parse(url.trim()).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
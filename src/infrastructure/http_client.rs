@@ -1,23 +1,23 @@
-use reqwest::{header, Client};
-use rss::Channel;
+use reqwest::{header, Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 
 /// HTTPクライアントのエラー型
 #[derive(Debug)]
 pub enum HttpClientError {
-    RequestError(reqwest::Error),
-    ParseError(String),
-    ResponseError(String),
+    Request(reqwest::Error),
+    Parse(String),
+    Response(String),
 }
 
 impl fmt::Display for HttpClientError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            HttpClientError::RequestError(e) => write!(f, "Request error: {}", e),
-            HttpClientError::ParseError(e) => write!(f, "Parse error: {}", e),
-            HttpClientError::ResponseError(e) => write!(f, "Response error: {}", e),
+            HttpClientError::Request(e) => write!(f, "Request error: {}", e),
+            HttpClientError::Parse(e) => write!(f, "Parse error: {}", e),
+            HttpClientError::Response(e) => write!(f, "Response error: {}", e),
         }
     }
 }
@@ -26,36 +26,113 @@ impl Error for HttpClientError {}
 
 impl From<reqwest::Error> for HttpClientError {
     fn from(err: reqwest::Error) -> Self {
-        HttpClientError::RequestError(err)
+        HttpClientError::Request(err)
     }
 }
 
-impl From<rss::Error> for HttpClientError {
-    fn from(err: rss::Error) -> Self {
-        HttpClientError::ParseError(err.to_string())
+impl From<serde_json::Error> for HttpClientError {
+    fn from(err: serde_json::Error) -> Self {
+        HttpClientError::Parse(err.to_string())
     }
 }
 
-impl From<serde_json::Error> for HttpClientError {
-    fn from(err: serde_json::Error) -> Self {
-        HttpClientError::ParseError(err.to_string())
+/// 条件付きGETに使用するキャッシュバリデーター
+#[derive(Debug, Clone, Default)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// バリデーターが1つも設定されていないかどうかを確認する
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// GETリクエストのレスポンスを表す構造体
+#[derive(Debug, Clone)]
+pub struct HttpGetResponse {
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    pub validators: CacheValidators,
+}
+
+/// 条件付きGETリクエストの結果を表す列挙型
+#[derive(Debug, Clone)]
+pub enum HttpGetResult {
+    /// 内容が更新されていた
+    Modified(HttpGetResponse),
+    /// 内容が更新されていなかった(304 Not Modified)
+    NotModified,
+}
+
+/// ステータスコードに関わらず取得したレスポンスを表す構造体
+#[derive(Debug, Clone)]
+pub struct HttpStatusResponse {
+    pub status: u16,
+    /// 小文字に正規化したヘッダー名とその値
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl HttpStatusResponse {
+    /// 成功を表すステータスコードかどうかを確認する
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// ヘッダーの値を取得する
+    ///
+    /// # Arguments
+    /// * `name` - ヘッダー名 (大文字・小文字は区別しない)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(|value| value.as_str())
     }
 }
 
 /// HTTPクライアントのトレイト
 pub trait HttpClient {
-    /// GETリクエストを送信し、RSSチャンネルを取得する
+    /// GETリクエストを送信し、Content-Typeとレスポンスボディを取得する
+    ///
+    /// バリデーターを指定した場合は If-None-Match / If-Modified-Since を付与した条件付きGETを行う
     ///
     /// # Arguments
     /// * `url` - リクエスト先のURL
-    fn get(&self, url: &str) -> impl std::future::Future<Output = Result<Channel, HttpClientError>> + Send;
+    /// * `validators` - 前回のレスポンスのキャッシュバリデーター
+    fn get(
+        &self,
+        url: &str,
+        validators: Option<&CacheValidators>,
+    ) -> impl std::future::Future<Output = Result<HttpGetResult, HttpClientError>> + Send;
 
-    /// POSTリクエストを送信する
+    /// POSTリクエストを送信し、ステータスコードとヘッダーを含むレスポンスを取得する
+    ///
+    /// 成功以外のステータスコードもエラーにせず、呼び出し側で判断する
     ///
     /// # Arguments
     /// * `url` - リクエスト先のURL
     /// * `body` - リクエストボディ
-    fn post<T: Serialize + ?Sized + Send + Sync>(&self, url: &str, body: &T) -> impl std::future::Future<Output = Result<(), HttpClientError>> + Send;
+    fn post_with_status<T: Serialize + ?Sized + Send + Sync>(
+        &self,
+        url: &str,
+        body: &T,
+    ) -> impl std::future::Future<Output = Result<HttpStatusResponse, HttpClientError>> + Send;
+
+    /// ヘッダーを指定してPUTリクエストを送信し、ステータスコードとヘッダーを含むレスポンスを取得する
+    ///
+    /// 成功以外のステータスコードもエラーにせず、呼び出し側で判断する
+    ///
+    /// # Arguments
+    /// * `url` - リクエスト先のURL
+    /// * `headers` - 追加するヘッダーの名前と値のリスト
+    /// * `body` - リクエストボディ
+    fn put_with_headers_and_status<T: Serialize + ?Sized + Send + Sync>(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &T,
+    ) -> impl std::future::Future<Output = Result<HttpStatusResponse, HttpClientError>> + Send;
 
     /// POSTリクエストを送信し、レスポンスを取得する
     ///
@@ -67,6 +144,19 @@ pub trait HttpClient {
         url: &str,
         body: &T,
     ) -> impl std::future::Future<Output = Result<R, HttpClientError>> + Send;
+
+    /// ヘッダーを指定してPOSTリクエストを送信し、レスポンスを取得する
+    ///
+    /// # Arguments
+    /// * `url` - リクエスト先のURL
+    /// * `headers` - 追加するヘッダーの名前と値のリスト
+    /// * `body` - リクエストボディ
+    fn post_with_headers_and_response<T: Serialize + ?Sized + Send + Sync, R: for<'de> Deserialize<'de> + Send>(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &T,
+    ) -> impl std::future::Future<Output = Result<R, HttpClientError>> + Send;
 }
 
 /// HTTPクライアントの実装
@@ -91,36 +181,88 @@ impl Default for HttpClientImpl {
 }
 
 impl HttpClient for HttpClientImpl {
-    fn get(&self, url: &str) -> impl std::future::Future<Output = Result<Channel, HttpClientError>> + Send {
-        async move {
-            let response = self.client.get(url).send().await?;
-            let content = response.bytes().await?;
-            let channel = Channel::read_from(&content[..])?;
-            Ok(channel)
+    async fn get(
+        &self,
+        url: &str,
+        validators: Option<&CacheValidators>,
+    ) -> Result<HttpGetResult, HttpClientError> {
+        let mut request = self.client.get(url);
+        if let Some(validators) = validators {
+            if let Some(etag) = &validators.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(HttpGetResult::NotModified);
         }
+
+        if !response.status().is_success() {
+            return Err(HttpClientError::Response(format!(
+                "Status: {}",
+                response.status()
+            )));
+        }
+
+        let header_value = |name: header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let content_type = header_value(header::CONTENT_TYPE);
+        let validators = CacheValidators {
+            etag: header_value(header::ETAG),
+            last_modified: header_value(header::LAST_MODIFIED),
+        };
+        let content = response.bytes().await?;
+
+        Ok(HttpGetResult::Modified(HttpGetResponse {
+            content_type,
+            body: content.to_vec(),
+            validators,
+        }))
     }
 
-    fn post<T: Serialize + ?Sized + Send + Sync>(&self, url: &str, body: &T) -> impl std::future::Future<Output = Result<(), HttpClientError>> + Send {
-        async move {
-            let response = self
-                .client
-                .post(url)
-                .header(header::CONTENT_TYPE, "application/json")
-                .json(body)
-                .send()
-                .await?;
-
-            if !response.status().is_success() {
-                let status = response.status();
-                let error_message = match response.text().await {
-                    Ok(text) => format!("Status: {}, Body: {}", status, text),
-                    Err(_) => format!("Status: {}, Body: <unable to read>", status),
-                };
-                return Err(HttpClientError::ResponseError(error_message));
-            }
+    async fn post_with_status<T: Serialize + ?Sized + Send + Sync>(
+        &self,
+        url: &str,
+        body: &T,
+    ) -> Result<HttpStatusResponse, HttpClientError> {
+        let response = self
+            .client
+            .post(url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(body)
+            .send()
+            .await?;
 
-            Ok(())
+        into_status_response(response).await
+    }
+
+    async fn put_with_headers_and_status<T: Serialize + ?Sized + Send + Sync>(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &T,
+    ) -> Result<HttpStatusResponse, HttpClientError> {
+        let mut request = self
+            .client
+            .put(url)
+            .header(header::CONTENT_TYPE, "application/json");
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
         }
+
+        let response = request.json(body).send().await?;
+
+        into_status_response(response).await
     }
 
     fn post_with_response<T: Serialize + ?Sized + Send + Sync, R: for<'de> Deserialize<'de> + Send>(
@@ -128,27 +270,61 @@ impl HttpClient for HttpClientImpl {
         url: &str,
         body: &T,
     ) -> impl std::future::Future<Output = Result<R, HttpClientError>> + Send {
-        async move {
-            let response = self
-                .client
-                .post(url)
-                .header(header::CONTENT_TYPE, "application/json")
-                .json(body)
-                .send()
-                .await?;
-
-            if !response.status().is_success() {
-                let status = response.status();
-                let error_message = match response.text().await {
-                    Ok(text) => format!("Status: {}, Body: {}", status, text),
-                    Err(_) => format!("Status: {}, Body: <unable to read>", status),
-                };
-                return Err(HttpClientError::ResponseError(error_message));
-            }
+        self.post_with_headers_and_response(url, &[], body)
+    }
 
-            let response_text = response.text().await?;
-            let response_json = serde_json::from_str(&response_text)?;
-            Ok(response_json)
+    async fn post_with_headers_and_response<T: Serialize + ?Sized + Send + Sync, R: for<'de> Deserialize<'de> + Send>(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: &T,
+    ) -> Result<R, HttpClientError> {
+        let mut request = self
+            .client
+            .post(url)
+            .header(header::CONTENT_TYPE, "application/json");
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let response = request.json(body).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_message = match response.text().await {
+                Ok(text) => format!("Status: {}, Body: {}", status, text),
+                Err(_) => format!("Status: {}, Body: <unable to read>", status),
+            };
+            return Err(HttpClientError::Response(error_message));
         }
+
+        let response_text = response.text().await?;
+        let response_json = serde_json::from_str(&response_text)?;
+        Ok(response_json)
     }
 }
+
+/// レスポンスからステータスコード・ヘッダー・ボディを取得する
+///
+/// # Arguments
+/// * `response` - レスポンス
+async fn into_status_response(response: reqwest::Response) -> Result<HttpStatusResponse, HttpClientError> {
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.as_str().to_string(), value.to_string()))
+        })
+        .collect();
+    let body = response.text().await?;
+
+    Ok(HttpStatusResponse {
+        status,
+        headers,
+        body,
+    })
+}
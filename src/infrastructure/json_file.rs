@@ -0,0 +1,19 @@
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// 値をJSONとしてファイルに書き込む
+///
+/// 書き込み途中で失敗しても既存のファイルが壊れないよう、一時ファイルに書き込んでから置き換える
+///
+/// # Arguments
+/// * `path` - 保存先のファイルパス
+/// * `value` - 保存する値
+pub fn write_json_atomically<V: Serialize + ?Sized>(path: &Path, value: &V) -> io::Result<()> {
+    let content = serde_json::to_string(value)?;
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
@@ -0,0 +1,129 @@
+use log::info;
+use reqwest::Url;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::domain::notification::{Notification, NotificationError, NotificationService};
+use crate::infrastructure::http_client::{HttpClient, HttpStatusResponse};
+use crate::infrastructure::matrix::payload::{build_messages, RoomMessage};
+use crate::infrastructure::webhook::{send_remaining, send_with_retry_after};
+
+/// Client-Server APIのエラーレスポンス
+#[derive(Debug, Deserialize)]
+struct MatrixErrorResponse {
+    /// 再送までの待ち時間 (ミリ秒)
+    #[serde(default)]
+    retry_after_ms: Option<u64>,
+}
+
+/// Matrix (Client-Server API) 通知サービスの実装
+pub struct MatrixNotificationService<T: HttpClient> {
+    http_client: T,
+    homeserver_url: Url,
+    access_token: String,
+    room_id: String,
+}
+
+impl<T: HttpClient> MatrixNotificationService<T> {
+    /// 新しいMatrix通知サービスを作成する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTPクライアント
+    /// * `homeserver_url` - ホームサーバーのURL
+    /// * `access_token` - 送信に使用するアカウントのアクセストークン
+    /// * `room_id` - 送信先のルームID (`!room:example.org`)
+    pub fn new(http_client: T, homeserver_url: &str, access_token: String, room_id: String) -> Result<Self, String> {
+        let homeserver_url = Url::parse(homeserver_url)
+            .map_err(|e| format!("Invalid Matrix homeserver URL {}: {}", homeserver_url, e))?;
+        if homeserver_url.cannot_be_a_base() {
+            return Err(format!("Invalid Matrix homeserver URL {}", homeserver_url));
+        }
+
+        Ok(Self {
+            http_client,
+            homeserver_url,
+            access_token,
+            room_id,
+        })
+    }
+
+    /// m.room.message イベントを送信するURLを作成する
+    ///
+    /// ルームIDとトランザクションIDはパスの1要素としてエンコードする
+    ///
+    /// # Arguments
+    /// * `transaction_id` - トランザクションID
+    fn send_url(&self, transaction_id: &str) -> String {
+        let mut url = self.homeserver_url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().extend([
+                "_matrix",
+                "client",
+                "v3",
+                "rooms",
+                self.room_id.as_str(),
+                "send",
+                "m.room.message",
+                transaction_id,
+            ]);
+        }
+        url.to_string()
+    }
+
+    /// ルームにメッセージを送信する
+    ///
+    /// 同じトランザクションIDで再送した場合、ホームサーバーは重複して投稿しない。
+    /// 429 Too Many Requests が返された場合は `retry_after_ms` の時間だけ待ってから再送する
+    ///
+    /// # Arguments
+    /// * `transaction_id` - トランザクションID
+    /// * `message` - 送信するメッセージ
+    async fn send_message(&self, transaction_id: &str, message: &RoomMessage) -> Result<(), NotificationError> {
+        let url = &self.send_url(transaction_id);
+        let headers = &[(
+            "Authorization".to_string(),
+            format!("Bearer {}", self.access_token),
+        )];
+
+        send_with_retry_after("Matrix homeserver", retry_after, || async move {
+            self.http_client
+                .put_with_headers_and_status(url, headers, message)
+                .await
+                .map_err(|e| NotificationError::SendError(e.to_string()))
+        })
+        .await
+        .map(|_| ())
+    }
+}
+
+/// 429 Too Many Requests のレスポンスボディから再送までの待ち時間を取得する
+///
+/// # Arguments
+/// * `response` - Client-Server APIのレスポンス
+fn retry_after(response: &HttpStatusResponse) -> Option<Duration> {
+    serde_json::from_str::<MatrixErrorResponse>(&response.body)
+        .ok()
+        .and_then(|error| error.retry_after_ms)
+        .map(Duration::from_millis)
+}
+
+impl<T: HttpClient + Send + Sync + 'static> NotificationService for MatrixNotificationService<T> {
+    async fn send_notifications(
+        &self,
+        _feed_url: &str,
+        delivery_id: &str,
+        sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError> {
+        // カテゴリごとのメッセージに変換
+        let messages = build_messages(&notifications);
+        info!("Sending {} Matrix messages to {}", messages.len(), self.room_id);
+
+        // Matrixに順番に送信
+        // トランザクションIDは送信ごとのIDとメッセージの順番から作成し、アウトボックスからの再送で同じ値になるようにする
+        send_remaining("Matrix", &messages, sent_messages, |index, message| async move {
+            self.send_message(&format!("{}-{}", delivery_id, index), message).await
+        })
+        .await
+    }
+}
@@ -0,0 +1,136 @@
+use serde::Serialize;
+
+use crate::domain::notification::{Notification, NotificationField};
+use crate::infrastructure::html::{escape_html, is_safe_link};
+use crate::infrastructure::text::CONTINUATION_SUFFIX;
+
+/// 1つのイベントの本文 (HTMLとテキストの合計) の最大バイト数
+///
+/// Matrixのイベントの上限 (65536バイト) より余裕を持たせた値
+const MAX_EVENT_BODY_BYTES: usize = 60_000;
+
+/// m.room.message イベントの内容
+#[derive(Serialize, Debug)]
+pub struct RoomMessage {
+    msgtype: &'static str,
+    /// HTMLを表示できないクライアント向けのテキスト
+    body: String,
+    format: &'static str,
+    formatted_body: String,
+}
+
+/// 通知をカテゴリごとのメッセージに変換する
+///
+/// 1つのカテゴリがイベントの上限を超える場合は、記事の単位で複数のメッセージに分割する
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn build_messages(notifications: &[Notification]) -> Vec<RoomMessage> {
+    let mut messages = Vec::new();
+
+    for notification in notifications {
+        let mut fields: Vec<&NotificationField> = Vec::new();
+        let mut title = notification.title.clone();
+
+        for field in &notification.fields {
+            fields.push(field);
+            if fields.len() > 1 && body_size(notification, &title, &fields) > MAX_EVENT_BODY_BYTES {
+                fields.pop();
+                messages.push(message(notification, &title, &fields));
+                fields = vec![field];
+                title = format!("{}{}", notification.title, CONTINUATION_SUFFIX);
+            }
+        }
+
+        messages.push(message(notification, &title, &fields));
+    }
+
+    messages
+}
+
+/// HTMLとテキストの本文の合計バイト数を取得する
+///
+/// # Arguments
+/// * `notification` - 通知
+/// * `title` - 見出しに表示するカテゴリ名
+/// * `fields` - メッセージに含める記事
+fn body_size(notification: &Notification, title: &str, fields: &[&NotificationField]) -> usize {
+    render_html(notification, title, fields).len() + render_plain_text(notification, title, fields).len()
+}
+
+/// カテゴリのメッセージを作成する
+///
+/// # Arguments
+/// * `notification` - 通知
+/// * `title` - 見出しに表示するカテゴリ名
+/// * `fields` - メッセージに含める記事
+fn message(notification: &Notification, title: &str, fields: &[&NotificationField]) -> RoomMessage {
+    RoomMessage {
+        msgtype: "m.text",
+        body: render_plain_text(notification, title, fields),
+        format: "org.matrix.custom.html",
+        formatted_body: render_html(notification, title, fields),
+    }
+}
+
+/// カテゴリをHTMLに変換する
+///
+/// # Arguments
+/// * `notification` - 通知
+/// * `title` - 見出しに表示するカテゴリ名
+/// * `fields` - メッセージに含める記事
+fn render_html(notification: &Notification, title: &str, fields: &[&NotificationField]) -> String {
+    let mut html = format!("<h3>{}</h3>", escape_html(title));
+    if let Some(source_name) = &notification.metadata.source_name {
+        let source = match notification.metadata.source_url.as_deref().filter(|url| is_safe_link(url)) {
+            Some(url) => format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(source_name)),
+            None => escape_html(source_name),
+        };
+        html.push_str(&format!("<p><em>{} ・ {}件の記事</em></p>", source, notification.fields.len()));
+    }
+
+    html.push_str("<ul>");
+    for field in fields {
+        let article_title = match field.url.as_deref().filter(|url| is_safe_link(url)) {
+            Some(url) => format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(&field.name)),
+            None => format!("<strong>{}</strong>", escape_html(&field.name)),
+        };
+        if field.value.trim().is_empty() {
+            html.push_str(&format!("<li>{}</li>", article_title));
+        } else {
+            html.push_str(&format!(
+                "<li>{}<br>{}</li>",
+                article_title,
+                escape_html(field.value.trim()).replace('\n', "<br>")
+            ));
+        }
+    }
+    html.push_str("</ul>");
+
+    html
+}
+
+/// カテゴリをテキストに変換する
+///
+/// # Arguments
+/// * `notification` - 通知
+/// * `title` - 見出しに表示するカテゴリ名
+/// * `fields` - メッセージに含める記事
+fn render_plain_text(notification: &Notification, title: &str, fields: &[&NotificationField]) -> String {
+    let mut text = format!("■ {}\n", title);
+    if let Some(source_name) = &notification.metadata.source_name {
+        text.push_str(&format!("{} ・ {}件の記事\n", source_name, notification.fields.len()));
+    }
+
+    for field in fields {
+        text.push_str(&format!("\n- {}\n", field.name));
+        if !field.value.trim().is_empty() {
+            text.push_str(&format!("  {}\n", field.value.trim().replace('\n', "\n  ")));
+        }
+        if let Some(url) = &field.url {
+            text.push_str(&format!("  {}\n", url));
+        }
+    }
+
+    text
+}
@@ -1,5 +1,20 @@
+pub mod ai_provider;
+pub mod cache;
+pub mod config;
 pub mod discord;
+pub mod email;
 pub mod event;
 pub mod gemini;
+pub mod html;
 pub mod http_client;
+pub mod json_file;
+pub mod matrix;
+pub mod notification_provider;
+pub mod ollama;
+pub mod openai;
 pub mod repository;
+pub mod slack;
+pub mod teams;
+pub mod telegram;
+pub mod text;
+pub mod webhook;
@@ -0,0 +1,140 @@
+use log::error;
+use std::env;
+
+use crate::domain::model::feed::FeedRegistry;
+use crate::domain::notification::{Notification, NotificationError, NotificationService};
+use crate::infrastructure::discord::notification_service::DiscordNotificationService;
+use crate::infrastructure::email::notification_service::{EmailNotificationService, SmtpConfig};
+use crate::infrastructure::http_client::HttpClient;
+use crate::infrastructure::matrix::notification_service::MatrixNotificationService;
+use crate::infrastructure::slack::notification_service::SlackNotificationService;
+use crate::infrastructure::teams::notification_service::TeamsNotificationService;
+use crate::infrastructure::telegram::notification_service::TelegramNotificationService;
+
+/// 設定で選択された通知サービス
+///
+/// `NOTIFIER` 環境変数で使用する通知先を切り替える
+pub enum NotificationProvider<T: HttpClient> {
+    Discord(DiscordNotificationService<T>),
+    Slack(SlackNotificationService<T>),
+    Teams(TeamsNotificationService<T>),
+    Email(EmailNotificationService),
+    Telegram(TelegramNotificationService<T>),
+    Matrix(MatrixNotificationService<T>),
+}
+
+impl<T: HttpClient> NotificationProvider<T> {
+    /// 環境変数の設定から通知サービスを作成する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTPクライアント
+    /// * `feed_registry` - フィードごとの通知先の設定を持つフィードレジストリ
+    pub fn from_env(http_client: T, feed_registry: &FeedRegistry) -> Result<Self, String> {
+        let notifier = env::var("NOTIFIER").unwrap_or_else(|_| "discord".to_string());
+
+        match notifier.as_str() {
+            "discord" => {
+                let webhook_url = env::var("DISCORD_WEBHOOK_URL").unwrap_or_else(|_| {
+                    error!("DISCORD_WEBHOOK_URL is not set");
+                    String::new()
+                });
+                Ok(NotificationProvider::Discord(DiscordNotificationService::new(
+                    http_client,
+                    webhook_url,
+                )))
+            }
+            "slack" => {
+                let webhook_url = env::var("SLACK_WEBHOOK_URL")
+                    .map_err(|_| "SLACK_WEBHOOK_URL is not set".to_string())?;
+                Ok(NotificationProvider::Slack(SlackNotificationService::new(
+                    http_client,
+                    webhook_url,
+                )))
+            }
+            "teams" => {
+                let webhook_url = env::var("TEAMS_WEBHOOK_URL")
+                    .map_err(|_| "TEAMS_WEBHOOK_URL is not set".to_string())?;
+                Ok(NotificationProvider::Teams(TeamsNotificationService::new(
+                    http_client,
+                    webhook_url,
+                )))
+            }
+            "email" => {
+                let config = SmtpConfig {
+                    host: env::var("SMTP_HOST").map_err(|_| "SMTP_HOST is not set".to_string())?,
+                    port: match env::var("SMTP_PORT") {
+                        Ok(port) => Some(
+                            port.parse()
+                                .map_err(|_| format!("SMTP_PORT is not a valid port: {}", port))?,
+                        ),
+                        Err(_) => None,
+                    },
+                    security: env::var("SMTP_SECURITY")
+                        .unwrap_or_else(|_| "starttls".to_string())
+                        .parse()?,
+                    credentials: match env::var("SMTP_USERNAME") {
+                        Ok(username) => Some((
+                            username,
+                            env::var("SMTP_PASSWORD")
+                                .map_err(|_| "SMTP_PASSWORD is not set while SMTP_USERNAME is set".to_string())?,
+                        )),
+                        Err(_) => None,
+                    },
+                    from: env::var("SMTP_FROM").map_err(|_| "SMTP_FROM is not set".to_string())?,
+                    to: env::var("SMTP_TO")
+                        .map_err(|_| "SMTP_TO is not set".to_string())?
+                        .split(',')
+                        .map(|address| address.trim().to_string())
+                        .filter(|address| !address.is_empty())
+                        .collect(),
+                };
+                Ok(NotificationProvider::Email(EmailNotificationService::new(config)?))
+            }
+            "telegram" => {
+                let api_base_url = env::var("TELEGRAM_API_BASE_URL")
+                    .unwrap_or_else(|_| "https://api.telegram.org".to_string());
+                Ok(NotificationProvider::Telegram(TelegramNotificationService::new(
+                    http_client,
+                    api_base_url,
+                    env::var("TELEGRAM_BOT_TOKEN").ok().filter(|token| !token.is_empty()),
+                    env::var("TELEGRAM_CHAT_ID").ok().filter(|chat_id| !chat_id.is_empty()),
+                    feed_registry.feeds(),
+                )?))
+            }
+            "matrix" => {
+                let homeserver_url = env::var("MATRIX_HOMESERVER_URL")
+                    .map_err(|_| "MATRIX_HOMESERVER_URL is not set".to_string())?;
+                let access_token = env::var("MATRIX_ACCESS_TOKEN")
+                    .map_err(|_| "MATRIX_ACCESS_TOKEN is not set".to_string())?;
+                let room_id = env::var("MATRIX_ROOM_ID")
+                    .map_err(|_| "MATRIX_ROOM_ID is not set".to_string())?;
+                Ok(NotificationProvider::Matrix(MatrixNotificationService::new(
+                    http_client,
+                    &homeserver_url,
+                    access_token,
+                    room_id,
+                )?))
+            }
+            other => Err(format!("Unknown NOTIFIER: {}", other)),
+        }
+    }
+}
+
+impl<T: HttpClient + Send + Sync + 'static> NotificationService for NotificationProvider<T> {
+    async fn send_notifications(
+        &self,
+        feed_url: &str,
+        delivery_id: &str,
+        sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError> {
+        match self {
+            NotificationProvider::Discord(service) => service.send_notifications(feed_url, delivery_id, sent_messages, notifications).await,
+            NotificationProvider::Slack(service) => service.send_notifications(feed_url, delivery_id, sent_messages, notifications).await,
+            NotificationProvider::Teams(service) => service.send_notifications(feed_url, delivery_id, sent_messages, notifications).await,
+            NotificationProvider::Email(service) => service.send_notifications(feed_url, delivery_id, sent_messages, notifications).await,
+            NotificationProvider::Telegram(service) => service.send_notifications(feed_url, delivery_id, sent_messages, notifications).await,
+            NotificationProvider::Matrix(service) => service.send_notifications(feed_url, delivery_id, sent_messages, notifications).await,
+        }
+    }
+}
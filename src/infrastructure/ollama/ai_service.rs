@@ -0,0 +1,99 @@
+use serde_json::Value;
+
+use crate::domain::ai_service::{AiRequest, AiResponse, AiService, AiServiceError};
+use crate::infrastructure::http_client::HttpClient;
+use crate::infrastructure::ollama::{OllamaChatRequest, OllamaChatResponse};
+
+/// Ollamaを使用するAIサービスの実装
+///
+/// フィードの内容を外部APIに送らず、ローカルのモデルで要約する場合に使用する
+pub struct OllamaAiService<T: HttpClient> {
+    http_client: T,
+    base_url: String,
+    model: String,
+    options: Option<Value>,
+    json_mode: bool,
+}
+
+impl<T: HttpClient> OllamaAiService<T> {
+    /// 新しいOllama AIサービスを作成する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTP クライアント
+    /// * `base_url` - OllamaのURL (例: `http://localhost:11434`)
+    /// * `model` - 使用するモデル名
+    /// * `options` - モデルのオプション (temperature、num_ctx など)
+    /// * `json_mode` - JSON形式での出力を強制するかどうか
+    pub fn new(
+        http_client: T,
+        base_url: String,
+        model: String,
+        options: Option<Value>,
+        json_mode: bool,
+    ) -> Self {
+        Self {
+            http_client,
+            base_url,
+            model,
+            options,
+            json_mode,
+        }
+    }
+
+    /// Chat APIのURLを取得する
+    fn chat_url(&self) -> String {
+        format!("{}/api/chat", self.base_url.trim_end_matches('/'))
+    }
+
+    /// レスポンスからテキストを抽出する
+    ///
+    /// # Arguments
+    /// * `response` - Ollama Chat APIのレスポンス
+    fn extract_text_from_response(&self, response: &OllamaChatResponse) -> Result<String, AiServiceError> {
+        if !response.done {
+            return Err(AiServiceError::ResponseError(
+                "Ollama response is not completed".to_string(),
+            ));
+        }
+
+        if response.message.content.is_empty() {
+            return Err(AiServiceError::ResponseError(
+                "Failed to extract text from response".to_string(),
+            ));
+        }
+
+        Ok(response.message.content.clone())
+    }
+}
+
+impl<T: HttpClient + Send + Sync + 'static> AiService for OllamaAiService<T> {
+    async fn process_request(&self, request: AiRequest) -> Result<AiResponse, AiServiceError> {
+        // Ollama リクエストの作成
+        let mut chat_request = OllamaChatRequest::new(self.model.clone(), request.prompt, request.context);
+        chat_request.options = self.options.clone();
+        if self.json_mode {
+            // スキーマが指定されている場合はスキーマに従った出力を強制する
+            chat_request.format = Some(
+                request
+                    .response_schema
+                    .unwrap_or_else(|| Value::String("json".to_string())),
+            );
+        }
+
+        // Ollama API へのリクエスト
+        let response: OllamaChatResponse = self
+            .http_client
+            .post_with_response(&self.chat_url(), &chat_request)
+            .await
+            .map_err(|e| AiServiceError::RequestError(e.to_string()))?;
+
+        // レスポンスからテキストを抽出
+        let content = self.extract_text_from_response(&response)?;
+
+        Ok(AiResponse { content })
+    }
+
+    fn model_name(&self) -> String {
+        format!("ollama/{}", self.model)
+    }
+}
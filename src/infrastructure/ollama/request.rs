@@ -0,0 +1,80 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Ollama Chat APIのメッセージを表す構造体
+#[derive(Serialize, Debug)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+/// Ollama Chat APIのリクエストを表す構造体
+#[derive(Serialize, Debug)]
+pub struct OllamaChatRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
+}
+
+impl OllamaChatRequest {
+    /// 新しいOllama Chat APIリクエストを作成する
+    ///
+    /// # Arguments
+    /// * `model` - 使用するモデル名
+    /// * `system` - システムメッセージ
+    /// * `user` - ユーザーメッセージ
+    pub fn new(model: String, system: String, user: String) -> Self {
+        // ユーザーのターンがないリクエストを受け付けないチャットテンプレートがあるため、
+        // ユーザーメッセージが空の場合はシステムメッセージの内容をユーザーメッセージとして送る
+        let (system, user) = if user.is_empty() {
+            (String::new(), system)
+        } else {
+            (system, user)
+        };
+
+        Self {
+            model,
+            // 空のメッセージを受け付けないサーバーがあるため、空のシステムメッセージは送らない
+            messages: vec![("system", system), ("user", user)]
+                .into_iter()
+                .filter(|(role, content)| *role == "user" || !content.is_empty())
+                .map(|(role, content)| Message {
+                    role: role.to_string(),
+                    content,
+                })
+                .collect(),
+            stream: false,
+            format: None,
+            options: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(request: &OllamaChatRequest) -> Vec<&str> {
+        request.messages.iter().map(|message| message.role.as_str()).collect()
+    }
+
+    #[test]
+    fn sends_prompt_as_user_message_when_context_is_empty() {
+        let request = OllamaChatRequest::new("model".to_string(), "prompt".to_string(), String::new());
+
+        assert_eq!(roles(&request), vec!["user"]);
+        assert_eq!(request.messages[0].content, "prompt");
+    }
+
+    #[test]
+    fn sends_prompt_as_system_message_with_context_as_user_message() {
+        let request = OllamaChatRequest::new("model".to_string(), "prompt".to_string(), "items".to_string());
+
+        assert_eq!(roles(&request), vec!["system", "user"]);
+        assert_eq!(request.messages[1].content, "items");
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Ollama Chat APIのレスポンスを表す構造体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OllamaChatResponse {
+    pub model: String,
+    pub message: Message,
+    pub done: bool,
+}
+
+/// Ollama Chat APIのレスポンスメッセージを表す構造体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
@@ -0,0 +1,119 @@
+use crate::domain::ai_service::{AiRequest, AiResponse, AiService, AiServiceError};
+use crate::infrastructure::http_client::HttpClient;
+use crate::infrastructure::openai::request::{JsonSchemaFormat, ResponseFormat};
+use crate::infrastructure::openai::{ChatCompletionRequest, ChatCompletionResponse};
+use serde_json::{Map, Value};
+
+/// OpenAI互換のChat Completions APIを使用するAIサービスの実装
+///
+/// vLLM、llama.cpp server、LM StudioなどOpenAI互換のエンドポイントで使用できる
+pub struct OpenAiCompatibleAiService<T: HttpClient> {
+    http_client: T,
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl<T: HttpClient> OpenAiCompatibleAiService<T> {
+    /// 新しいOpenAI互換AIサービスを作成する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTP クライアント
+    /// * `base_url` - APIのベースURL (例: `http://localhost:8000/v1`)
+    /// * `model` - 使用するモデル名
+    /// * `api_key` - APIキー (不要なサーバーの場合は `None`)
+    pub fn new(http_client: T, base_url: String, model: String, api_key: Option<String>) -> Self {
+        Self {
+            http_client,
+            base_url,
+            model,
+            api_key,
+        }
+    }
+
+    /// Chat Completions APIのURLを取得する
+    fn chat_completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+
+    /// リクエストに付与するヘッダーを取得する
+    fn headers(&self) -> Vec<(String, String)> {
+        self.api_key
+            .iter()
+            .map(|api_key| ("Authorization".to_string(), format!("Bearer {}", api_key)))
+            .collect()
+    }
+
+    /// レスポンスからテキストを抽出する
+    ///
+    /// # Arguments
+    /// * `response` - Chat Completions APIのレスポンス
+    fn extract_text_from_response(&self, response: &ChatCompletionResponse) -> Result<String, AiServiceError> {
+        response
+            .choices
+            .iter()
+            .filter_map(|choice| choice.message.content.clone())
+            .find(|content| !content.is_empty())
+            .ok_or_else(|| {
+                AiServiceError::ResponseError("Failed to extract text from response".to_string())
+            })
+    }
+}
+
+impl<T: HttpClient + Send + Sync + 'static> AiService for OpenAiCompatibleAiService<T> {
+    async fn process_request(&self, request: AiRequest) -> Result<AiResponse, AiServiceError> {
+        if self.base_url.is_empty() {
+            return Err(AiServiceError::RequestError("OPENAI_BASE_URL is empty".to_string()));
+        }
+
+        // Chat Completions リクエストの作成
+        let mut chat_request = ChatCompletionRequest::new(self.model.clone(), request.prompt, request.context);
+        chat_request.response_format = request.response_schema.map(|schema| ResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: "response".to_string(),
+                schema: to_strict_schema(schema),
+                strict: true,
+            },
+        });
+
+        // Chat Completions API へのリクエスト
+        let response: ChatCompletionResponse = self
+            .http_client
+            .post_with_headers_and_response(&self.chat_completions_url(), &self.headers(), &chat_request)
+            .await
+            .map_err(|e| AiServiceError::RequestError(e.to_string()))?;
+
+        // レスポンスからテキストを抽出
+        let content = self.extract_text_from_response(&response)?;
+
+        Ok(AiResponse { content })
+    }
+
+    fn model_name(&self) -> String {
+        format!("openai/{}", self.model)
+    }
+}
+
+/// JSON SchemaをStructured Outputsのstrictモードで受け付けられる形式に変換する
+///
+/// strictモードでは全てのオブジェクトに `additionalProperties: false` の指定が必要なため、これを追加する
+///
+/// # Arguments
+/// * `schema` - JSON Schema
+fn to_strict_schema(schema: Value) -> Value {
+    match schema {
+        Value::Object(map) => {
+            let is_object = map.get("type").and_then(Value::as_str) == Some("object");
+            let mut map: Map<String, Value> = map
+                .into_iter()
+                .map(|(key, value)| (key, to_strict_schema(value)))
+                .collect();
+            if is_object {
+                map.entry("additionalProperties").or_insert(Value::Bool(false));
+            }
+            Value::Object(map)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(to_strict_schema).collect()),
+        other => other,
+    }
+}
@@ -0,0 +1,6 @@
+pub mod ai_service;
+pub mod request;
+pub mod response;
+
+pub use request::ChatCompletionRequest;
+pub use response::ChatCompletionResponse;
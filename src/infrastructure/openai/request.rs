@@ -0,0 +1,90 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Chat Completions APIのメッセージを表す構造体
+#[derive(Serialize, Debug)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+/// 構造化出力のJSON Schemaを表す構造体
+#[derive(Serialize, Debug)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub schema: Value,
+    pub strict: bool,
+}
+
+/// 出力形式の指定を表す列挙型
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+/// Chat Completions APIのリクエストを表す構造体
+#[derive(Serialize, Debug)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+}
+
+impl ChatCompletionRequest {
+    /// 新しいChat Completions APIリクエストを作成する
+    ///
+    /// # Arguments
+    /// * `model` - 使用するモデル名
+    /// * `system` - システムメッセージ
+    /// * `user` - ユーザーメッセージ
+    pub fn new(model: String, system: String, user: String) -> Self {
+        // ユーザーのターンがないリクエストを受け付けないチャットテンプレートがあるため、
+        // ユーザーメッセージが空の場合はシステムメッセージの内容をユーザーメッセージとして送る
+        let (system, user) = if user.is_empty() {
+            (String::new(), system)
+        } else {
+            (system, user)
+        };
+
+        Self {
+            model,
+            // 空のメッセージを受け付けないサーバーがあるため、空のシステムメッセージは送らない
+            messages: vec![("system", system), ("user", user)]
+                .into_iter()
+                .filter(|(role, content)| *role == "user" || !content.is_empty())
+                .map(|(role, content)| Message {
+                    role: role.to_string(),
+                    content,
+                })
+                .collect(),
+            response_format: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(request: &ChatCompletionRequest) -> Vec<&str> {
+        request.messages.iter().map(|message| message.role.as_str()).collect()
+    }
+
+    #[test]
+    fn sends_prompt_as_user_message_when_context_is_empty() {
+        let request = ChatCompletionRequest::new("model".to_string(), "prompt".to_string(), String::new());
+
+        assert_eq!(roles(&request), vec!["user"]);
+        assert_eq!(request.messages[0].content, "prompt");
+    }
+
+    #[test]
+    fn sends_prompt_as_system_message_with_context_as_user_message() {
+        let request = ChatCompletionRequest::new("model".to_string(), "prompt".to_string(), "items".to_string());
+
+        assert_eq!(roles(&request), vec!["system", "user"]);
+        assert_eq!(request.messages[1].content, "items");
+    }
+}
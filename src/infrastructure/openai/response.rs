@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Chat Completions APIのレスポンスを表す構造体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChatCompletionResponse {
+    pub choices: Vec<Choice>,
+}
+
+/// Chat Completions APIのレスポンス候補を表す構造体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Choice {
+    pub message: Message,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// Chat Completions APIのレスポンスメッセージを表す構造体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    #[serde(default)]
+    pub content: Option<String>,
+}
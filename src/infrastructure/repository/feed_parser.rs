@@ -0,0 +1,164 @@
+use atom_syndication::{Entry, Feed, Link};
+use rss::{Channel, ChannelBuilder, Guid, ImageBuilder, Item, ItemBuilder};
+
+use crate::domain::repository::rss_repository::RssRepositoryError;
+use crate::infrastructure::repository::json_feed::JsonFeed;
+
+/// フィードの形式を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+/// Content-Typeまたはドキュメントの形からフィードの形式を判定する
+///
+/// # Arguments
+/// * `content_type` - レスポンスのContent-Type
+/// * `content` - フィードのドキュメント
+pub fn detect_format(content_type: Option<&str>, content: &[u8]) -> FeedFormat {
+    // application/feed+json や application/json はJSON Feedとして扱う
+    if content_type.is_some_and(|content_type| content_type.contains("json")) {
+        return FeedFormat::Json;
+    }
+
+    // BOMと空白を除いた先頭の文字で判定する
+    let body = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+    if body.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{') {
+        return FeedFormat::Json;
+    }
+
+    match root_element_name(content) {
+        Some(name) if name == "feed" || name.ends_with(":feed") => FeedFormat::Atom,
+        _ => FeedFormat::Rss,
+    }
+}
+
+/// フィードのドキュメントを解析し、RSSチャンネルに正規化する
+///
+/// # Arguments
+/// * `content_type` - レスポンスのContent-Type
+/// * `content` - フィードのドキュメント
+pub fn parse_feed(content_type: Option<&str>, content: &[u8]) -> Result<Channel, RssRepositoryError> {
+    match detect_format(content_type, content) {
+        FeedFormat::Rss => {
+            Channel::read_from(content).map_err(|e| RssRepositoryError::ParseError(e.to_string()))
+        }
+        FeedFormat::Atom => Feed::read_from(content)
+            .map(|feed| atom_to_channel(&feed))
+            .map_err(|e| RssRepositoryError::ParseError(e.to_string())),
+        FeedFormat::Json => {
+            let feed: JsonFeed = serde_json::from_slice(content)
+                .map_err(|e| RssRepositoryError::ParseError(e.to_string()))?;
+            if !feed.has_valid_version() {
+                return Err(RssRepositoryError::ParseError(format!(
+                    "unsupported JSON Feed version: {}",
+                    feed.version
+                )));
+            }
+            Ok(feed.to_channel())
+        }
+    }
+}
+
+/// XML宣言・コメント・DOCTYPEを読み飛ばし、ルート要素の名前を取得する
+///
+/// # Arguments
+/// * `content` - XMLドキュメント
+fn root_element_name(content: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(content);
+    let mut rest = text.as_ref();
+
+    loop {
+        let start = rest.find('<')?;
+        rest = &rest[start + 1..];
+
+        if rest.starts_with('?') || rest.starts_with('!') {
+            // 処理命令・コメント・DOCTYPEは読み飛ばす
+            let end = if rest.starts_with("!--") {
+                rest.find("-->")? + 3
+            } else {
+                rest.find('>')? + 1
+            };
+            rest = &rest[end..];
+            continue;
+        }
+
+        let name: String = rest
+            .chars()
+            .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+            .collect();
+        return Some(name);
+    }
+}
+
+/// AtomフィードをRSSチャンネルに変換する
+///
+/// # Arguments
+/// * `feed` - Atomフィード
+fn atom_to_channel(feed: &Feed) -> Channel {
+    let items: Vec<Item> = feed.entries().iter().map(atom_entry_to_item).collect();
+    let link = alternate_link(feed.links()).unwrap_or_default();
+
+    // logoがない場合はiconをフィードの画像として使用する
+    let image = feed.logo().or_else(|| feed.icon()).map(|url| {
+        ImageBuilder::default()
+            .url(url.to_string())
+            .title(feed.title().value.clone())
+            .link(link.clone())
+            .build()
+    });
+
+    ChannelBuilder::default()
+        .title(feed.title().value.clone())
+        .link(link)
+        .image(image)
+        .description(
+            feed.subtitle()
+                .map(|subtitle| subtitle.value.clone())
+                .unwrap_or_default(),
+        )
+        .items(items)
+        .build()
+}
+
+/// Atomエントリーを RSSアイテムに変換する
+///
+/// # Arguments
+/// * `entry` - Atomエントリー
+fn atom_entry_to_item(entry: &Entry) -> Item {
+    // summaryがない場合は本文を説明として使用する
+    let description = entry
+        .summary()
+        .map(|summary| summary.value.clone())
+        .or_else(|| entry.content().and_then(|content| content.value.clone()));
+
+    let pub_date = entry
+        .published()
+        .unwrap_or_else(|| entry.updated())
+        .to_rfc2822();
+
+    ItemBuilder::default()
+        .title(Some(entry.title().value.clone()))
+        .link(alternate_link(entry.links()))
+        .description(description)
+        .guid(Some(Guid {
+            value: entry.id().to_string(),
+            permalink: false,
+        }))
+        .pub_date(Some(pub_date))
+        .build()
+}
+
+/// rel="alternate" のリンクを優先してリンクを取得する
+///
+/// # Arguments
+/// * `links` - Atomのリンクのリスト
+fn alternate_link(links: &[Link]) -> Option<String> {
+    links
+        .iter()
+        .find(|link| link.rel() == "alternate")
+        .or_else(|| links.first())
+        .map(|link| link.href().to_string())
+}
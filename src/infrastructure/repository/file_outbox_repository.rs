@@ -0,0 +1,74 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::domain::model::outbox::OutboxEntry;
+use crate::domain::repository::outbox_repository::{OutboxRepository, OutboxRepositoryError};
+use crate::infrastructure::json_file::write_json_atomically;
+
+/// JSONファイルに送信できなかった通知を保存するアウトボックスの実装
+pub struct FileOutboxRepository {
+    path: PathBuf,
+    entries: Mutex<Vec<OutboxEntry>>,
+}
+
+impl FileOutboxRepository {
+    /// ファイルからエントリーを読み込み、アウトボックスを作成する
+    ///
+    /// # Arguments
+    /// * `path` - 保存先のファイルパス
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, OutboxRepositoryError> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                OutboxRepositoryError::ReadError(format!("{}: {}", path.display(), e))
+            })?,
+            // ファイルがまだない場合は空の状態から始める
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(OutboxRepositoryError::ReadError(format!(
+                    "{}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        Ok(Self {
+            path,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// 現在の状態をファイルに書き込む
+    ///
+    /// # Arguments
+    /// * `entries` - 保存するエントリー
+    fn persist(&self, entries: &[OutboxEntry]) -> Result<(), OutboxRepositoryError> {
+        write_json_atomically(&self.path, entries)
+            .map_err(|e| OutboxRepositoryError::WriteError(format!("{}: {}", self.path.display(), e)))
+    }
+}
+
+impl OutboxRepository for FileOutboxRepository {
+    fn save(&self, entry: &OutboxEntry) -> Result<(), OutboxRepositoryError> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.iter_mut().find(|existing| existing.id == entry.id) {
+            Some(existing) => *existing = entry.clone(),
+            None => entries.push(entry.clone()),
+        }
+        self.persist(&entries)
+    }
+
+    fn list(&self) -> Result<Vec<OutboxEntry>, OutboxRepositoryError> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.clone())
+    }
+
+    fn remove(&self, id: &str) -> Result<(), OutboxRepositoryError> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.id != id);
+        self.persist(&entries)
+    }
+}
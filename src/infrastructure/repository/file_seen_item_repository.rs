@@ -0,0 +1,68 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::domain::repository::seen_item_repository::{SeenItemRepository, SeenItemRepositoryError};
+use crate::infrastructure::json_file::write_json_atomically;
+
+/// JSONファイルに通知済みアイテムを保存するリポジトリの実装
+pub struct FileSeenItemRepository {
+    path: PathBuf,
+    items: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl FileSeenItemRepository {
+    /// ファイルから通知済みアイテムを読み込み、リポジトリを作成する
+    ///
+    /// # Arguments
+    /// * `path` - 保存先のファイルパス
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, SeenItemRepositoryError> {
+        let path = path.into();
+        let items = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                SeenItemRepositoryError::ReadError(format!("{}: {}", path.display(), e))
+            })?,
+            // ファイルがまだない場合は空の状態から始める
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                return Err(SeenItemRepositoryError::ReadError(format!(
+                    "{}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        Ok(Self {
+            path,
+            items: Mutex::new(items),
+        })
+    }
+
+    /// 現在の状態をファイルに書き込む
+    ///
+    /// # Arguments
+    /// * `items` - 保存する通知済みアイテム
+    fn persist(&self, items: &HashMap<String, HashSet<String>>) -> Result<(), SeenItemRepositoryError> {
+        write_json_atomically(&self.path, items)
+            .map_err(|e| SeenItemRepositoryError::WriteError(format!("{}: {}", self.path.display(), e)))
+    }
+}
+
+impl SeenItemRepository for FileSeenItemRepository {
+    fn is_seen(&self, feed_url: &str, key: &str) -> Result<bool, SeenItemRepositoryError> {
+        let items = self.items.lock().unwrap();
+        Ok(items.get(feed_url).is_some_and(|keys| keys.contains(key)))
+    }
+
+    fn mark_seen(&self, feed_url: &str, keys: &[String]) -> Result<(), SeenItemRepositoryError> {
+        let mut items = self.items.lock().unwrap();
+        items
+            .entry(feed_url.to_string())
+            .or_default()
+            .extend(keys.iter().cloned());
+        self.persist(&items)
+    }
+}
@@ -1,14 +1,21 @@
 use async_trait::async_trait;
-use log::error;
+use log::{error, info};
 use rss::Channel;
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 use crate::domain::model::rss_data::RssData;
-use crate::domain::repository::rss_repository::{RssRepository, RssRepositoryError};
-use crate::infrastructure::http_client::{HttpClient, HttpClientError};
+use crate::domain::repository::rss_repository::{FeedFetchResult, RssRepository, RssRepositoryError};
+use crate::infrastructure::http_client::{CacheValidators, HttpClient, HttpClientError, HttpGetResult};
+use crate::infrastructure::repository::feed_parser::parse_feed;
 
 /// HTTPを使用したRSSリポジトリの実装
 pub struct HttpRssRepository<T: HttpClient> {
     http_client: T,
+    /// 処理が完了したフィードのキャッシュバリデーター
+    validators: Mutex<HashMap<String, CacheValidators>>,
+    /// 取得済みで処理が完了していないフィードのキャッシュバリデーター
+    pending_validators: Mutex<HashMap<String, CacheValidators>>,
 }
 
 impl<T: HttpClient> HttpRssRepository<T> {
@@ -17,19 +24,54 @@ impl<T: HttpClient> HttpRssRepository<T> {
     /// # Arguments
     /// * `http_client` - HTTPクライアント
     pub fn new(http_client: T) -> Self {
-        Self { http_client }
+        Self {
+            http_client,
+            validators: Mutex::new(HashMap::new()),
+            pending_validators: Mutex::new(HashMap::new()),
+        }
     }
 }
 
 #[async_trait]
 impl<T: HttpClient + Send + Sync + 'static> RssRepository for HttpRssRepository<T> {
-    async fn fetch_feed(&self, url: &str) -> Result<Channel, RssRepositoryError> {
-        match self.http_client.get(url).await {
-            Ok(channel) => Ok(channel),
+    async fn fetch_feed(&self, url: &str) -> Result<FeedFetchResult, RssRepositoryError> {
+        let validators = self.validators.lock().unwrap().get(url).cloned();
+
+        let response = match self.http_client.get(url, validators.as_ref()).await {
+            Ok(HttpGetResult::Modified(response)) => response,
+            Ok(HttpGetResult::NotModified) => {
+                info!("Feed {} is not modified", url);
+                return Ok(FeedFetchResult::NotModified);
+            }
             Err(e) => {
                 error!("Failed to fetch RSS feed: {}", e);
-                Err(RssRepositoryError::FetchError(e.to_string()))
+                return Err(RssRepositoryError::FetchError(e.to_string()));
             }
+        };
+
+        // RSS/Atom/JSON Feedを判定してRSSチャンネルに正規化
+        let channel = parse_feed(response.content_type.as_deref(), &response.body).map_err(|e| {
+            error!("Failed to parse feed from {}: {}", url, e);
+            e
+        })?;
+
+        // 処理が完了するまでは次回の条件付き取得に使用しない
+        if !response.validators.is_empty() {
+            self.pending_validators
+                .lock()
+                .unwrap()
+                .insert(url.to_string(), response.validators);
+        }
+
+        Ok(FeedFetchResult::Fetched(Box::new(channel)))
+    }
+
+    fn mark_feed_processed(&self, url: &str) {
+        if let Some(validators) = self.pending_validators.lock().unwrap().remove(url) {
+            self.validators
+                .lock()
+                .unwrap()
+                .insert(url.to_string(), validators);
         }
     }
 
@@ -49,9 +91,9 @@ impl<T: HttpClient + Send + Sync + 'static> RssRepository for HttpRssRepository<
 impl From<HttpClientError> for RssRepositoryError {
     fn from(error: HttpClientError) -> Self {
         match error {
-            HttpClientError::RequestError(e) => RssRepositoryError::FetchError(e.to_string()),
-            HttpClientError::ParseError(e) => RssRepositoryError::ParseError(e),
-            HttpClientError::ResponseError(e) => RssRepositoryError::FetchError(e),
+            HttpClientError::Request(e) => RssRepositoryError::FetchError(e.to_string()),
+            HttpClientError::Parse(e) => RssRepositoryError::ParseError(e),
+            HttpClientError::Response(e) => RssRepositoryError::FetchError(e),
         }
     }
 }
@@ -0,0 +1,105 @@
+use chrono::DateTime;
+use rss::{Channel, ChannelBuilder, Enclosure, Guid, ImageBuilder, Item, ItemBuilder};
+use serde::Deserialize;
+
+/// JSON Feed (https://jsonfeed.org/version/1.1) を表す構造体
+#[derive(Debug, Deserialize)]
+pub struct JsonFeed {
+    pub version: String,
+    pub title: String,
+    #[serde(default)]
+    pub home_page_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub items: Vec<JsonFeedItem>,
+}
+
+/// JSON Feedのアイテムを表す構造体
+#[derive(Debug, Deserialize)]
+pub struct JsonFeedItem {
+    pub id: serde_json::Value,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub external_url: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub content_text: Option<String>,
+    #[serde(default)]
+    pub date_published: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+impl JsonFeed {
+    /// JSON Feedのバージョン表記が有効かどうかを確認する
+    pub fn has_valid_version(&self) -> bool {
+        self.version.starts_with("https://jsonfeed.org/version/")
+    }
+
+    /// JSON FeedをRSSチャンネルに変換する
+    pub fn to_channel(&self) -> Channel {
+        let items: Vec<Item> = self.items.iter().map(JsonFeedItem::to_item).collect();
+        let image = self.icon.as_ref().map(|icon| {
+            ImageBuilder::default()
+                .url(icon.clone())
+                .title(self.title.clone())
+                .link(self.home_page_url.clone().unwrap_or_default())
+                .build()
+        });
+
+        ChannelBuilder::default()
+            .title(self.title.clone())
+            .link(self.home_page_url.clone().unwrap_or_default())
+            .image(image)
+            .description(self.description.clone().unwrap_or_default())
+            .items(items)
+            .build()
+    }
+}
+
+impl JsonFeedItem {
+    /// JSON FeedのアイテムをRSSアイテムに変換する
+    pub fn to_item(&self) -> Item {
+        // summaryがない場合は本文のテキストを説明として使用する
+        let description = self.summary.clone().or_else(|| self.content_text.clone());
+
+        // 仕様上idは文字列だが、数値を使用しているフィードもあるため両方受け付ける
+        let id = match &self.id {
+            serde_json::Value::String(id) => id.clone(),
+            other => other.to_string(),
+        };
+
+        // RFC 3339の日付をRSSのRFC 2822形式に変換する
+        let pub_date = self.date_published.as_ref().map(|date| {
+            DateTime::parse_from_rfc3339(date)
+                .map(|date| date.to_rfc2822())
+                .unwrap_or_else(|_| date.clone())
+        });
+
+        // 記事の画像はRSSと同様に画像のenclosureとして扱う
+        let enclosure = self.image.as_ref().map(|image| Enclosure {
+            url: image.clone(),
+            length: "0".to_string(),
+            mime_type: "image/*".to_string(),
+        });
+
+        ItemBuilder::default()
+            .title(self.title.clone())
+            .link(self.url.clone().or_else(|| self.external_url.clone()))
+            .enclosure(enclosure)
+            .description(description)
+            .guid(Some(Guid {
+                value: id,
+                permalink: false,
+            }))
+            .pub_date(pub_date)
+            .build()
+    }
+}
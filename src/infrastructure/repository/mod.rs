@@ -1 +1,5 @@
+pub mod feed_parser;
+pub mod file_outbox_repository;
+pub mod file_seen_item_repository;
 pub mod http_rss_repository;
+pub mod json_feed;
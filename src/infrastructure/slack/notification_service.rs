@@ -0,0 +1,48 @@
+use log::info;
+
+use crate::domain::notification::{Notification, NotificationError, NotificationService};
+use crate::infrastructure::http_client::HttpClient;
+use crate::infrastructure::slack::payload::build_messages;
+use crate::infrastructure::webhook::{post_with_retry_after, send_remaining};
+
+/// Slack (Incoming Webhook) 通知サービスの実装
+pub struct SlackNotificationService<T: HttpClient> {
+    http_client: T,
+    webhook_url: String,
+}
+
+impl<T: HttpClient> SlackNotificationService<T> {
+    /// 新しいSlack通知サービスを作成する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTPクライアント
+    /// * `webhook_url` - SlackのIncoming Webhook URL
+    pub fn new(http_client: T, webhook_url: String) -> Self {
+        Self {
+            http_client,
+            webhook_url,
+        }
+    }
+}
+
+impl<T: HttpClient + Send + Sync + 'static> NotificationService for SlackNotificationService<T> {
+    async fn send_notifications(
+        &self,
+        _feed_url: &str,
+        _delivery_id: &str,
+        sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError> {
+        // 通知をSlackの制限に収まるメッセージに分割
+        let messages = build_messages(&notifications);
+        if messages.len() > 1 {
+            info!("Splitting notifications into {} Slack messages", messages.len());
+        }
+
+        // Slackに順番に送信
+        send_remaining("Slack", &messages, sent_messages, |_, message| {
+            post_with_retry_after(&self.http_client, &self.webhook_url, message, "Slack webhook")
+        })
+        .await
+    }
+}
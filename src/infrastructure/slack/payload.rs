@@ -0,0 +1,305 @@
+use serde::Serialize;
+
+use crate::domain::notification::{Notification, NotificationField};
+use crate::infrastructure::text::{truncate, truncate_escaped, CONTINUATION_SUFFIX};
+
+/// 1つのメッセージに含められるブロックの最大数
+const MAX_BLOCKS_PER_MESSAGE: usize = 50;
+/// headerブロックのテキストの最大文字数
+const MAX_HEADER_LENGTH: usize = 150;
+/// sectionブロックのテキストの最大文字数
+const MAX_SECTION_TEXT_LENGTH: usize = 3000;
+/// リンクに使用するURLのエスケープ後の最大文字数 (超える場合はリンクにしない)
+const MAX_URL_LENGTH: usize = 1000;
+/// 通知のプレビューに表示するテキストの最大文字数
+const MAX_FALLBACK_TEXT_LENGTH: usize = 3000;
+
+/// Block Kitのテキストオブジェクトを表す構造体
+#[derive(Serialize, Debug, Clone)]
+struct SlackText {
+    #[serde(rename = "type")]
+    text_type: &'static str,
+    text: String,
+}
+
+/// Block Kitのブロックを表す列挙型
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum SlackBlock {
+    Header { text: SlackText },
+    Section { text: SlackText },
+    Context { elements: Vec<SlackText> },
+}
+
+/// Slackに送信する1つのメッセージを表す構造体
+#[derive(Serialize, Debug)]
+pub struct SlackMessage {
+    /// ブロックを表示できない環境や通知のプレビューに使われるテキスト
+    text: String,
+    blocks: Vec<SlackBlock>,
+}
+
+/// 通知をSlackの制限に収まるメッセージのリストに変換する
+///
+/// カテゴリごとにheaderブロックを置き、記事ごとにリンク付きのsectionブロックを続ける。
+/// ブロック数が上限を超える場合は記事を捨てずに複数のメッセージに分割する
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn build_messages(notifications: &[Notification]) -> Vec<SlackMessage> {
+    let mut messages = Vec::new();
+    let mut blocks: Vec<SlackBlock> = Vec::new();
+    let mut titles: Vec<String> = Vec::new();
+
+    for notification in notifications {
+        let context = context_block(notification);
+        let heading = |title: &str| {
+            let mut heading = vec![header_block(title)];
+            heading.extend(context.clone());
+            heading
+        };
+
+        // 見出しだけのメッセージにならないよう、見出しは最初の記事と同じメッセージに入れる
+        let mut pending_heading = Some(heading(&notification.title));
+        for field in &notification.fields {
+            let needed = pending_heading.as_ref().map_or(0, Vec::len) + 1;
+            if !blocks.is_empty() && blocks.len() + needed > MAX_BLOCKS_PER_MESSAGE {
+                messages.push(message(std::mem::take(&mut blocks), &mut titles));
+                if pending_heading.is_none() {
+                    pending_heading = Some(heading(&format!("{}{}", notification.title, CONTINUATION_SUFFIX)));
+                }
+            }
+
+            if let Some(mut heading_blocks) = pending_heading.take() {
+                titles.push(notification.title.clone());
+                blocks.append(&mut heading_blocks);
+            }
+            blocks.push(section_block(field));
+        }
+
+        // 記事のないカテゴリも見出しだけは表示する
+        if let Some(mut heading_blocks) = pending_heading.take() {
+            if !blocks.is_empty() && blocks.len() + heading_blocks.len() > MAX_BLOCKS_PER_MESSAGE {
+                messages.push(message(std::mem::take(&mut blocks), &mut titles));
+            }
+            titles.push(notification.title.clone());
+            blocks.append(&mut heading_blocks);
+        }
+    }
+
+    if !blocks.is_empty() {
+        messages.push(message(blocks, &mut titles));
+    }
+
+    messages
+}
+
+/// ブロックからメッセージを作成する
+///
+/// # Arguments
+/// * `blocks` - メッセージに含めるブロック
+/// * `titles` - メッセージに含まれるカテゴリ名 (作成後は空になる)
+fn message(blocks: Vec<SlackBlock>, titles: &mut Vec<String>) -> SlackMessage {
+    let text = truncate(&titles.join(" / "), MAX_FALLBACK_TEXT_LENGTH, "Slack fallback text");
+    titles.clear();
+    SlackMessage { text, blocks }
+}
+
+/// カテゴリの見出しのheaderブロックを作成する
+///
+/// # Arguments
+/// * `title` - カテゴリ名
+fn header_block(title: &str) -> SlackBlock {
+    SlackBlock::Header {
+        text: SlackText {
+            text_type: "plain_text",
+            text: truncate(title, MAX_HEADER_LENGTH, "Slack header"),
+        },
+    }
+}
+
+/// フィード名と記事数のcontextブロックを作成する
+///
+/// # Arguments
+/// * `notification` - 通知
+fn context_block(notification: &Notification) -> Option<SlackBlock> {
+    let metadata = &notification.metadata;
+    let source = metadata.source_name.as_ref()?;
+    let url = link_url(metadata.source_url.as_deref());
+    let article_count = format!(" ・ {}件の記事", notification.fields.len());
+
+    // リンクの記法 `<url|name>` と記事数の分を除いた文字数をフィード名に使う
+    let budget = MAX_SECTION_TEXT_LENGTH.saturating_sub(
+        article_count.chars().count() + url.as_ref().map_or(0, |url| url.chars().count() + 3),
+    );
+    let name = truncate_escaped(source, budget, "Slack context", escape);
+    let source = match url {
+        Some(url) => format!("<{}|{}>", url, name),
+        None => name,
+    };
+
+    Some(SlackBlock::Context {
+        elements: vec![SlackText {
+            text_type: "mrkdwn",
+            text: format!("{}{}", source, article_count),
+        }],
+    })
+}
+
+/// 記事のsectionブロックを作成する
+///
+/// リンクが切れないよう、長すぎる場合は記事名と説明を切り詰める。
+/// 文字参照の途中で切れないよう、エスケープ前の文字列を切り詰める
+///
+/// # Arguments
+/// * `field` - 記事の通知フィールド
+fn section_block(field: &NotificationField) -> SlackBlock {
+    let url = link_url(field.url.as_deref());
+
+    // リンクの記法 `*<url|name>*` の分を除いた文字数を記事名に使う
+    let name_budget = MAX_SECTION_TEXT_LENGTH.saturating_sub(url.as_ref().map_or(2, |url| url.chars().count() + 5));
+    let name = truncate_escaped(&field.name, name_budget, "Slack section title", escape);
+    let title = match url {
+        Some(url) => format!("*<{}|{}>*", url, name),
+        None => format!("*{}*", name),
+    };
+
+    let budget = MAX_SECTION_TEXT_LENGTH.saturating_sub(title.chars().count() + 1);
+    let description = truncate_escaped(&field.value, budget, "Slack section text", escape);
+    let text = if description.is_empty() {
+        title
+    } else {
+        format!("{}\n{}", title, description)
+    };
+
+    SlackBlock::Section {
+        text: SlackText {
+            text_type: "mrkdwn",
+            text,
+        },
+    }
+}
+
+/// リンクに使用するエスケープ済みのURLを取得する
+///
+/// 長すぎるURLはテキストの上限を超えてしまうため、リンクにしない
+///
+/// # Arguments
+/// * `url` - 対象のURL
+fn link_url(url: Option<&str>) -> Option<String> {
+    url.map(escape).filter(|url| url.chars().count() <= MAX_URL_LENGTH)
+}
+
+/// mrkdwnで制御文字として扱われる文字をエスケープする
+///
+/// # Arguments
+/// * `text` - 対象の文字列
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::notification::NotificationMetadata;
+
+    fn field(name: &str, value: &str, url: &str) -> NotificationField {
+        NotificationField {
+            name: name.to_string(),
+            value: value.to_string(),
+            url: Some(url.to_string()),
+        }
+    }
+
+    fn notification(title: &str, fields: Vec<NotificationField>) -> Notification {
+        Notification {
+            title: title.to_string(),
+            fields,
+            metadata: NotificationMetadata {
+                source_name: Some("Example".to_string()),
+                source_url: Some("https://example.com".to_string()),
+                ..NotificationMetadata::default()
+            },
+        }
+    }
+
+    fn block_text(block: &SlackBlock) -> &str {
+        match block {
+            SlackBlock::Header { text } | SlackBlock::Section { text } => &text.text,
+            SlackBlock::Context { elements } => &elements[0].text,
+        }
+    }
+
+    fn assert_within_limits(messages: &[SlackMessage]) {
+        for message in messages {
+            assert!(message.blocks.len() <= MAX_BLOCKS_PER_MESSAGE);
+            assert!(message.text.chars().count() <= MAX_FALLBACK_TEXT_LENGTH);
+            for block in &message.blocks {
+                let limit = match block {
+                    SlackBlock::Header { .. } => MAX_HEADER_LENGTH,
+                    _ => MAX_SECTION_TEXT_LENGTH,
+                };
+                assert!(block_text(block).chars().count() <= limit);
+            }
+        }
+    }
+
+    fn sections(messages: &[SlackMessage]) -> Vec<&str> {
+        messages
+            .iter()
+            .flat_map(|message| &message.blocks)
+            .filter(|block| matches!(block, SlackBlock::Section { .. }))
+            .map(block_text)
+            .collect()
+    }
+
+    #[test]
+    fn splits_blocks_across_messages_without_losing_articles() {
+        let fields = (0..120)
+            .map(|i| field(&format!("article-{:03}", i), "text", &format!("https://example.com/{}", i)))
+            .collect();
+
+        let messages = build_messages(&[notification("Rust", fields)]);
+
+        assert_eq!(messages.len(), 3);
+        assert_within_limits(&messages);
+        let sections = sections(&messages);
+        assert_eq!(sections.len(), 120);
+        for (i, section) in sections.iter().enumerate() {
+            assert!(section.contains(&format!("article-{:03}", i)));
+        }
+
+        // 続きのメッセージは見出しから始まる
+        for message in &messages[1..] {
+            assert_eq!(block_text(&message.blocks[0]), format!("Rust{}", CONTINUATION_SUFFIX));
+        }
+    }
+
+    #[test]
+    fn keeps_text_within_the_limit_after_escaping() {
+        let long_url = format!("https://example.com/{}", "a".repeat(5000));
+        let fields = vec![
+            field(&"<&>".repeat(2000), &"<&>".repeat(2000), "https://example.com/1"),
+            field("long url", "text", &long_url),
+        ];
+
+        let messages = build_messages(&[notification(&"t".repeat(300), fields)]);
+
+        assert_within_limits(&messages);
+        let sections = sections(&messages);
+        assert!(sections[0].starts_with("*<https://example.com/1|&lt;&amp;&gt;"));
+        assert!(!sections[0].contains("&amp…") && !sections[0].contains("&g…"));
+        assert_eq!(sections[1], "*long url*\ntext");
+    }
+
+    #[test]
+    fn shows_the_heading_of_a_category_without_articles() {
+        let messages = build_messages(&[notification("Empty", Vec::new())]);
+
+        assert_within_limits(&messages);
+        assert_eq!(block_text(&messages[0].blocks[0]), "Empty");
+        assert_eq!(messages[0].text, "Empty");
+    }
+}
@@ -0,0 +1,340 @@
+use serde::Serialize;
+
+use crate::domain::notification::{Notification, NotificationField};
+use crate::infrastructure::text::{truncate, CONTINUATION_SUFFIX};
+
+/// 1つのメッセージのJSONの最大バイト数
+///
+/// Teamsのメッセージの上限 (約28KB) より余裕を持たせた値
+const MAX_PAYLOAD_BYTES: usize = 25_000;
+
+/// カテゴリ名・フィード名・記事のタイトルの最大文字数
+///
+/// 以下の上限は、すべての文字がJSONで6バイトになる場合でも
+/// 記事1件とその見出しが `MAX_PAYLOAD_BYTES` に収まるように決めている
+const MAX_TITLE_LENGTH: usize = 256;
+/// 記事の説明の最大文字数
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+/// ボタンに使用するURLの最大文字数 (超える場合はボタンを表示しない)
+const MAX_URL_LENGTH: usize = 1000;
+
+/// Adaptive Cardのバージョン
+const ADAPTIVE_CARD_VERSION: &str = "1.4";
+/// Adaptive Cardのスキーマ
+const ADAPTIVE_CARD_SCHEMA: &str = "http://adaptivecards.io/schemas/adaptive-card.json";
+/// Adaptive Cardの添付ファイルのContent-Type
+const ADAPTIVE_CARD_CONTENT_TYPE: &str = "application/vnd.microsoft.card.adaptive";
+
+/// 記事を開くボタンのラベル
+const OPEN_URL_TITLE: &str = "この記事を読む";
+/// Adaptive Cardの要素を表す列挙型
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type")]
+enum CardElement {
+    Container {
+        style: &'static str,
+        items: Vec<CardElement>,
+    },
+    TextBlock {
+        text: String,
+        wrap: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        weight: Option<&'static str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        size: Option<&'static str>,
+        #[serde(rename = "isSubtle", skip_serializing_if = "Option::is_none")]
+        is_subtle: Option<bool>,
+    },
+    ActionSet {
+        actions: Vec<CardAction>,
+    },
+}
+
+/// Adaptive Cardのアクションを表す構造体
+#[derive(Serialize, Debug, Clone)]
+struct CardAction {
+    #[serde(rename = "type")]
+    action_type: &'static str,
+    title: String,
+    url: String,
+}
+
+/// Adaptive Cardを表す構造体
+#[derive(Serialize, Debug, Clone)]
+struct AdaptiveCard {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    #[serde(rename = "type")]
+    card_type: &'static str,
+    version: &'static str,
+    body: Vec<CardElement>,
+}
+
+/// メッセージに添付するAdaptive Cardを表す構造体
+#[derive(Serialize, Debug, Clone)]
+struct CardAttachment {
+    #[serde(rename = "contentType")]
+    content_type: &'static str,
+    content: AdaptiveCard,
+}
+
+/// Teamsに送信する1つのメッセージを表す構造体
+#[derive(Serialize, Debug, Clone)]
+pub struct TeamsMessage {
+    #[serde(rename = "type")]
+    message_type: &'static str,
+    attachments: Vec<CardAttachment>,
+}
+
+impl TeamsMessage {
+    /// 空のカードを持つメッセージを作成する
+    fn new() -> Self {
+        Self {
+            message_type: "message",
+            attachments: vec![CardAttachment {
+                content_type: ADAPTIVE_CARD_CONTENT_TYPE,
+                content: AdaptiveCard {
+                    schema: ADAPTIVE_CARD_SCHEMA,
+                    card_type: "AdaptiveCard",
+                    version: ADAPTIVE_CARD_VERSION,
+                    body: Vec::new(),
+                },
+            }],
+        }
+    }
+
+    /// カードの本文を取得する
+    fn body(&self) -> &Vec<CardElement> {
+        &self.attachments[0].content.body
+    }
+
+    /// カードの本文を変更可能な参照として取得する
+    fn body_mut(&mut self) -> &mut Vec<CardElement> {
+        &mut self.attachments[0].content.body
+    }
+
+    /// JSONにしたときのバイト数を取得する
+    fn payload_size(&self) -> usize {
+        serde_json::to_vec(self).map(|json| json.len()).unwrap_or(usize::MAX)
+    }
+}
+
+/// 通知をTeamsの制限に収まるメッセージのリストに変換する
+///
+/// カテゴリごとにContainerを作り、記事ごとにタイトル・説明・リンクのボタンを入れる。
+/// メッセージのサイズが上限を超える場合は記事を捨てずに複数のカードに分割する
+///
+/// # Arguments
+/// * `notifications` - 送信する通知のリスト
+pub fn build_messages(notifications: &[Notification]) -> Vec<TeamsMessage> {
+    let mut messages = Vec::new();
+    let mut current = TeamsMessage::new();
+    let mut articles_in_message = 0;
+
+    for notification in notifications {
+        current.body_mut().push(container(notification, &notification.title));
+        let mut articles_in_container = 0;
+
+        for field in &notification.fields {
+            let items = article_items(field);
+            push_items(&mut current, items.clone());
+
+            // 上限を超えた場合はこの記事を取り除き、続きを新しいカードに入れる
+            if current.payload_size() > MAX_PAYLOAD_BYTES && articles_in_message > 0 {
+                pop_items(&mut current, items.len());
+                let title = if articles_in_container == 0 {
+                    // 見出しだけのContainerを前のカードに残さない
+                    current.body_mut().pop();
+                    notification.title.clone()
+                } else {
+                    format!("{}{}", notification.title, CONTINUATION_SUFFIX)
+                };
+                messages.push(std::mem::replace(&mut current, TeamsMessage::new()));
+
+                current.body_mut().push(container(notification, &title));
+                push_items(&mut current, items);
+                articles_in_message = 0;
+                articles_in_container = 0;
+            }
+
+            articles_in_message += 1;
+            articles_in_container += 1;
+        }
+    }
+
+    if !current.body().is_empty() {
+        messages.push(current);
+    }
+
+    messages
+}
+
+/// カテゴリのContainerを見出しだけの状態で作成する
+///
+/// # Arguments
+/// * `notification` - 通知
+/// * `title` - 見出しに表示するカテゴリ名
+fn container(notification: &Notification, title: &str) -> CardElement {
+    let mut items = vec![CardElement::TextBlock {
+        text: truncate(title, MAX_TITLE_LENGTH, "Teams category"),
+        wrap: true,
+        weight: Some("Bolder"),
+        size: Some("Large"),
+        is_subtle: None,
+    }];
+
+    if let Some(source_name) = &notification.metadata.source_name {
+        items.push(CardElement::TextBlock {
+            text: format!(
+                "{} ・ {}件の記事",
+                truncate(source_name, MAX_TITLE_LENGTH, "Teams feed name"),
+                notification.fields.len()
+            ),
+            wrap: true,
+            weight: None,
+            size: Some("Small"),
+            is_subtle: Some(true),
+        });
+    }
+
+    CardElement::Container {
+        style: "emphasis",
+        items,
+    }
+}
+
+/// 記事を表す要素を作成する
+///
+/// # Arguments
+/// * `field` - 記事の通知フィールド
+fn article_items(field: &NotificationField) -> Vec<CardElement> {
+    let mut items = vec![CardElement::TextBlock {
+        text: truncate(&field.name, MAX_TITLE_LENGTH, "Teams article title"),
+        wrap: true,
+        weight: Some("Bolder"),
+        size: None,
+        is_subtle: None,
+    }];
+
+    if !field.value.trim().is_empty() {
+        items.push(CardElement::TextBlock {
+            text: truncate(&field.value, MAX_DESCRIPTION_LENGTH, "Teams article description"),
+            wrap: true,
+            weight: None,
+            size: None,
+            is_subtle: None,
+        });
+    }
+
+    if let Some(url) = field.url.as_ref().filter(|url| url.chars().count() <= MAX_URL_LENGTH) {
+        items.push(CardElement::ActionSet {
+            actions: vec![CardAction {
+                action_type: "Action.OpenUrl",
+                title: OPEN_URL_TITLE.to_string(),
+                url: url.clone(),
+            }],
+        });
+    }
+
+    items
+}
+
+/// 最後のContainerに要素を追加する
+///
+/// # Arguments
+/// * `message` - 対象のメッセージ
+/// * `items` - 追加する要素
+fn push_items(message: &mut TeamsMessage, items: Vec<CardElement>) {
+    if let Some(CardElement::Container { items: container_items, .. }) = message.body_mut().last_mut() {
+        container_items.extend(items);
+    }
+}
+
+/// 最後のContainerから要素を取り除く
+///
+/// # Arguments
+/// * `message` - 対象のメッセージ
+/// * `count` - 取り除く要素の数
+fn pop_items(message: &mut TeamsMessage, count: usize) {
+    if let Some(CardElement::Container { items, .. }) = message.body_mut().last_mut() {
+        items.truncate(items.len().saturating_sub(count));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::notification::NotificationMetadata;
+
+    fn field(name: &str, value: &str, url: &str) -> NotificationField {
+        NotificationField {
+            name: name.to_string(),
+            value: value.to_string(),
+            url: Some(url.to_string()),
+        }
+    }
+
+    fn notification(title: &str, fields: Vec<NotificationField>) -> Notification {
+        Notification {
+            title: title.to_string(),
+            fields,
+            metadata: NotificationMetadata {
+                source_name: Some("Example".to_string()),
+                ..NotificationMetadata::default()
+            },
+        }
+    }
+
+    /// カードに含まれるテキストを順番に取得する
+    fn texts(messages: &[TeamsMessage]) -> Vec<String> {
+        fn collect(element: &CardElement, texts: &mut Vec<String>) {
+            match element {
+                CardElement::Container { items, .. } => items.iter().for_each(|item| collect(item, texts)),
+                CardElement::TextBlock { text, .. } => texts.push(text.clone()),
+                CardElement::ActionSet { .. } => {}
+            }
+        }
+
+        let mut texts = Vec::new();
+        for element in messages.iter().flat_map(TeamsMessage::body) {
+            collect(element, &mut texts);
+        }
+        texts
+    }
+
+    #[test]
+    fn splits_cards_by_payload_size_without_losing_articles() {
+        let fields = (0..60)
+            .map(|i| field(&format!("article-{:02}", i), &"説明".repeat(500), &format!("https://example.com/{}", i)))
+            .collect();
+
+        let messages = build_messages(&[notification("Rust", fields)]);
+
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|message| message.payload_size() <= MAX_PAYLOAD_BYTES));
+        let texts = texts(&messages);
+        let articles: Vec<&String> = texts.iter().filter(|text| text.starts_with("article-")).collect();
+        assert_eq!(articles.len(), 60);
+        for (i, article) in articles.iter().enumerate() {
+            assert_eq!(**article, format!("article-{:02}", i));
+        }
+        let continuation = format!("Rust{}", CONTINUATION_SUFFIX);
+        assert_eq!(texts.iter().filter(|text| **text == continuation).count(), messages.len() - 1);
+    }
+
+    #[test]
+    fn keeps_a_single_oversized_article_within_the_limit() {
+        // 制御文字はJSONで `\u0001` の6バイトになる
+        let long_text = "\u{1}".repeat(50_000);
+        let long_url = format!("https://example.com/{}", "\u{1}".repeat(5000));
+        let fields = vec![
+            field(&long_text, &long_text, &long_url),
+            field("short", "text", "https://example.com/short"),
+        ];
+
+        let messages = build_messages(&[notification(&long_text, fields)]);
+
+        assert!(messages.iter().all(|message| message.payload_size() <= MAX_PAYLOAD_BYTES));
+        assert!(texts(&messages).contains(&"short".to_string()));
+    }
+}
@@ -0,0 +1,154 @@
+use log::info;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::domain::model::feed::Feed;
+use crate::domain::notification::{Notification, NotificationError, NotificationService};
+use crate::infrastructure::http_client::{HttpClient, HttpStatusResponse};
+use crate::infrastructure::telegram::payload::{build_messages, SendMessageRequest};
+use crate::infrastructure::webhook::{send_remaining, send_with_retry_after};
+
+/// Bot APIのエラーレスポンス
+#[derive(Debug, Deserialize)]
+struct TelegramErrorResponse {
+    #[serde(default)]
+    parameters: Option<TelegramResponseParameters>,
+}
+
+/// Bot APIのエラーレスポンスの追加情報
+#[derive(Debug, Deserialize)]
+struct TelegramResponseParameters {
+    /// 再送までの待ち時間 (秒)
+    #[serde(default)]
+    retry_after: Option<u64>,
+}
+
+/// Telegramの送信先を表す構造体
+#[derive(Debug, Clone)]
+struct TelegramChat {
+    bot_token: String,
+    chat_id: String,
+}
+
+/// Telegram (Bot API) 通知サービスの実装
+pub struct TelegramNotificationService<T: HttpClient> {
+    http_client: T,
+    api_base_url: String,
+    /// フィードの設定がない場合に使用する送信先
+    default_chat: Option<TelegramChat>,
+    /// フィードのURLごとの送信先
+    feed_chats: HashMap<String, TelegramChat>,
+}
+
+impl<T: HttpClient> TelegramNotificationService<T> {
+    /// 新しいTelegram通知サービスを作成する
+    ///
+    /// フィードに `telegram` の設定がある場合はその値を、ない項目は既定の値を使用する
+    ///
+    /// # Arguments
+    /// * `http_client` - HTTPクライアント
+    /// * `api_base_url` - Bot APIのURL
+    /// * `bot_token` - 既定のBotトークン
+    /// * `chat_id` - 既定のチャットID
+    /// * `feeds` - 登録されているフィード
+    pub fn new(
+        http_client: T,
+        api_base_url: String,
+        bot_token: Option<String>,
+        chat_id: Option<String>,
+        feeds: &[Feed],
+    ) -> Result<Self, String> {
+        let mut feed_chats = HashMap::new();
+        for feed in feeds {
+            let target = feed.telegram.as_ref();
+            let bot_token = target
+                .and_then(|target| target.bot_token.clone())
+                .or_else(|| bot_token.clone())
+                .ok_or_else(|| format!("No Telegram bot token is configured for feed '{}'", feed.name))?;
+            let chat_id = target
+                .and_then(|target| target.chat_id.clone())
+                .or_else(|| chat_id.clone())
+                .ok_or_else(|| format!("No Telegram chat id is configured for feed '{}'", feed.name))?;
+            feed_chats.insert(feed.url.clone(), TelegramChat { bot_token, chat_id });
+        }
+
+        let default_chat = bot_token
+            .zip(chat_id)
+            .map(|(bot_token, chat_id)| TelegramChat { bot_token, chat_id });
+
+        Ok(Self {
+            http_client,
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            default_chat,
+            feed_chats,
+        })
+    }
+
+    /// sendMessageでメッセージを送信する
+    ///
+    /// 429 Too Many Requests が返された場合は `retry_after` の時間だけ待ってから再送する
+    ///
+    /// # Arguments
+    /// * `chat` - 送信先
+    /// * `message` - 送信するメッセージ
+    async fn send_message(&self, chat: &TelegramChat, message: &SendMessageRequest) -> Result<(), NotificationError> {
+        let url = &format!("{}/bot{}/sendMessage", self.api_base_url, chat.bot_token);
+
+        send_with_retry_after("Telegram Bot API", retry_after, || async move {
+            self.http_client
+                .post_with_status(url, message)
+                .await
+                .map_err(|e| NotificationError::SendError(e.to_string()))
+        })
+        .await
+        .map(|_| ())
+        // エラーメッセージにBotトークンを含めない
+        .map_err(|e| match e {
+            NotificationError::SendError(error) => NotificationError::SendError(error.replace(&chat.bot_token, "***")),
+            NotificationError::PartiallySent { sent_messages, error } => NotificationError::PartiallySent {
+                sent_messages,
+                error: error.replace(&chat.bot_token, "***"),
+            },
+        })
+    }
+}
+
+/// 429 Too Many Requests のレスポンスボディから再送までの待ち時間を取得する
+///
+/// # Arguments
+/// * `response` - Bot APIのレスポンス
+fn retry_after(response: &HttpStatusResponse) -> Option<Duration> {
+    serde_json::from_str::<TelegramErrorResponse>(&response.body)
+        .ok()
+        .and_then(|error| error.parameters)
+        .and_then(|parameters| parameters.retry_after)
+        .map(Duration::from_secs)
+}
+
+impl<T: HttpClient + Send + Sync + 'static> NotificationService for TelegramNotificationService<T> {
+    async fn send_notifications(
+        &self,
+        feed_url: &str,
+        _delivery_id: &str,
+        sent_messages: usize,
+        notifications: Vec<Notification>,
+    ) -> Result<(), NotificationError> {
+        let chat = self
+            .feed_chats
+            .get(feed_url)
+            .or(self.default_chat.as_ref())
+            .ok_or_else(|| {
+                NotificationError::SendError(format!("No Telegram chat is configured for {}", feed_url))
+            })?;
+
+        // 通知を4096文字以内のメッセージに分割
+        let messages = build_messages(&chat.chat_id, &notifications);
+        if messages.len() > 1 {
+            info!("Splitting notifications into {} Telegram messages", messages.len());
+        }
+
+        // Telegramに順番に送信
+        send_remaining("Telegram", &messages, sent_messages, |_, message| self.send_message(chat, message)).await
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Serialize;
+
+use crate::domain::notification::{Notification, NotificationField};
+use crate::infrastructure::html::{escape_html, is_safe_link};
+use crate::infrastructure::text::truncate_escaped;
+
+/// 1つのメッセージの最大文字数
+///
+/// Telegramはエンティティを解析した後のUTF-16の長さで数えるため、HTMLのままで数えれば必ず収まる
+const MAX_MESSAGE_LENGTH: usize = 4096;
+/// カテゴリ名・フィード名・記事のタイトルのエスケープ後の最大文字数
+///
+/// 以下の上限は、すべての文字がUTF-16で2単位になる場合でも1つのブロックが
+/// `MAX_MESSAGE_LENGTH` に収まるように決めている
+const MAX_TITLE_LENGTH: usize = 256;
+/// リンクに使用するURLのエスケープ後の最大文字数 (超える場合はリンクにしない)
+const MAX_URL_LENGTH: usize = 512;
+/// 1つの記事に表示する説明のエスケープ後の最大文字数
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+
+/// sendMessageのリクエストボディ
+#[derive(Serialize, Debug)]
+pub struct SendMessageRequest {
+    chat_id: String,
+    text: String,
+    parse_mode: &'static str,
+    disable_web_page_preview: bool,
+}
+
+/// 通知を4096文字以内のHTMLのメッセージに分割する
+///
+/// タグが途中で切れないよう、カテゴリの見出しと記事の単位で分割する
+///
+/// # Arguments
+/// * `chat_id` - 送信先のチャットID
+/// * `notifications` - 送信する通知のリスト
+pub fn build_messages(chat_id: &str, notifications: &[Notification]) -> Vec<SendMessageRequest> {
+    let blocks = notifications.iter().flat_map(|notification| {
+        std::iter::once(heading(notification)).chain(notification.fields.iter().map(article))
+    });
+
+    let mut texts: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for block in blocks {
+        let separator = if current.is_empty() { 0 } else { 2 };
+        if !current.is_empty() && utf16_len(&current) + separator + utf16_len(&block) > MAX_MESSAGE_LENGTH {
+            texts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(&block);
+    }
+    if !current.is_empty() {
+        texts.push(current);
+    }
+
+    texts
+        .into_iter()
+        .map(|text| SendMessageRequest {
+            chat_id: chat_id.to_string(),
+            text,
+            parse_mode: "HTML",
+            disable_web_page_preview: true,
+        })
+        .collect()
+}
+
+/// カテゴリの見出しを作成する
+///
+/// # Arguments
+/// * `notification` - 通知
+fn heading(notification: &Notification) -> String {
+    let title = truncate_escaped(&notification.title, MAX_TITLE_LENGTH, "Telegram category", escape_html);
+    let mut heading = format!("<b>■ {}</b>", title);
+    if let Some(source_name) = &notification.metadata.source_name {
+        heading.push_str(&format!(
+            "\n<i>{} ・ {}件の記事</i>",
+            truncate_escaped(source_name, MAX_TITLE_LENGTH, "Telegram feed name", escape_html),
+            notification.fields.len()
+        ));
+    }
+    heading
+}
+
+/// 記事のリンクと説明を作成する
+///
+/// # Arguments
+/// * `field` - 記事の通知フィールド
+fn article(field: &NotificationField) -> String {
+    let name = truncate_escaped(&field.name, MAX_TITLE_LENGTH, "Telegram article title", escape_html);
+    let url = field
+        .url
+        .as_deref()
+        .filter(|url| is_safe_link(url))
+        .map(escape_html)
+        .filter(|url| url.chars().count() <= MAX_URL_LENGTH);
+    let title = match url {
+        Some(url) => format!("<a href=\"{}\">{}</a>", url, name),
+        None => format!("<b>{}</b>", name),
+    };
+
+    let description = truncate_escaped(
+        field.value.trim(),
+        MAX_DESCRIPTION_LENGTH,
+        "Telegram article description",
+        escape_html,
+    );
+    if description.is_empty() {
+        title
+    } else {
+        format!("{}\n{}", title, description)
+    }
+}
+
+/// Telegramが数える単位 (UTF-16) での文字列の長さを取得する
+///
+/// # Arguments
+/// * `text` - 対象の文字列
+fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::notification::NotificationMetadata;
+
+    fn field(name: &str, value: &str, url: &str) -> NotificationField {
+        NotificationField {
+            name: name.to_string(),
+            value: value.to_string(),
+            url: Some(url.to_string()),
+        }
+    }
+
+    fn notification(title: &str, fields: Vec<NotificationField>) -> Notification {
+        Notification {
+            title: title.to_string(),
+            fields,
+            metadata: NotificationMetadata {
+                source_name: Some("Example".to_string()),
+                ..NotificationMetadata::default()
+            },
+        }
+    }
+
+    #[test]
+    fn splits_articles_across_messages_without_losing_any() {
+        let fields = (0..40)
+            .map(|i| field(&format!("article-{:02}", i), &"説明".repeat(200), &format!("https://example.com/{}", i)))
+            .collect();
+        let messages = build_messages("1", &[notification("Rust", fields)]);
+
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|message| utf16_len(&message.text) <= MAX_MESSAGE_LENGTH));
+        let text: String = messages.iter().map(|message| message.text.as_str()).collect();
+        for i in 0..40 {
+            assert_eq!(text.matches(&format!("article-{:02}", i)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn keeps_every_block_within_the_limit_for_long_titles_and_urls() {
+        // 絵文字はUTF-16で2単位、`&` はエスケープで5文字になる
+        let long_text = "😀&".repeat(5000);
+        let long_url = format!("https://example.com/{}", "a".repeat(5000));
+        let fields = vec![
+            field(&long_text, &long_text, &long_url),
+            field("short", "", "https://example.com/short"),
+        ];
+        let messages = build_messages("1", &[notification(&long_text, fields)]);
+
+        assert!(messages.iter().all(|message| utf16_len(&message.text) <= MAX_MESSAGE_LENGTH));
+        let text: String = messages.iter().map(|message| message.text.as_str()).collect();
+        assert!(!text.contains(&long_url));
+        assert!(text.contains("<a href=\"https://example.com/short\">short</a>"));
+    }
+}
@@ -0,0 +1,65 @@
+use log::warn;
+
+/// 分割したカテゴリの見出しに付ける接尾辞
+pub const CONTINUATION_SUFFIX: &str = " (続き)";
+/// 省略したことを示す記号
+pub const ELLIPSIS: char = '…';
+
+/// 文字列が最大文字数を超える場合は省略記号を付けて切り詰める
+///
+/// # Arguments
+/// * `text` - 対象の文字列
+/// * `max_length` - 最大文字数
+/// * `label` - ログに出力する対象の名前
+pub fn truncate(text: &str, max_length: usize, label: &str) -> String {
+    let length = text.chars().count();
+    if length <= max_length {
+        return text.to_string();
+    }
+
+    warn!("Truncating {} from {} to {} characters", label, length, max_length);
+    if max_length == 0 {
+        return String::new();
+    }
+    text.chars()
+        .take(max_length - 1)
+        .chain(std::iter::once(ELLIPSIS))
+        .collect()
+}
+
+/// エスケープ後の文字数が最大文字数に収まるよう切り詰めてからエスケープする
+///
+/// エスケープ後の文字列を切り詰めると文字参照の途中で切れてしまうため、
+/// エスケープによって増える文字数を考慮してエスケープ前の文字列を切り詰める
+///
+/// # Arguments
+/// * `text` - 対象の文字列
+/// * `max_length` - エスケープ後の最大文字数
+/// * `label` - ログに出力する対象の名前
+/// * `escape` - 文字ごとにエスケープする関数
+pub fn truncate_escaped(text: &str, max_length: usize, label: &str, escape: fn(&str) -> String) -> String {
+    let escaped = escape(text);
+    let length = escaped.chars().count();
+    if length <= max_length {
+        return escaped;
+    }
+
+    warn!("Truncating {} from {} to {} characters", label, length, max_length);
+    if max_length == 0 {
+        return String::new();
+    }
+
+    let mut truncated = String::new();
+    let mut truncated_length = 0;
+    for c in text.chars() {
+        let escaped = escape(c.encode_utf8(&mut [0; 4]));
+        let escaped_length = escaped.chars().count();
+        if truncated_length + escaped_length > max_length - 1 {
+            break;
+        }
+        truncated_length += escaped_length;
+        truncated.push_str(&escaped);
+    }
+    truncated.push(ELLIPSIS);
+    truncated
+}
@@ -0,0 +1,191 @@
+use actix_web::rt;
+use log::{info, warn};
+use serde::Serialize;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::domain::notification::NotificationError;
+use crate::infrastructure::http_client::{HttpClient, HttpStatusResponse};
+
+/// レート制限を受けたときに再送する最大回数
+const MAX_RATE_LIMIT_RETRIES: usize = 3;
+
+/// レート制限で待機する最大時間
+///
+/// これより長く待つ必要がある場合は送信を失敗とし、アウトボックスからの再送に任せる
+pub const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Retry-After ヘッダーから再送までの待ち時間を取得する
+///
+/// # Arguments
+/// * `response` - 429 Too Many Requests のレスポンス
+pub fn retry_after_header(response: &HttpStatusResponse) -> Option<Duration> {
+    response
+        .header("Retry-After")
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// レート制限に従ってリクエストを送信する
+///
+/// 429 Too Many Requests が返された場合は `retry_after` で取得した時間だけ待ってから再送する。
+/// 待ち時間が分からない場合は1秒待つ
+///
+/// # Arguments
+/// * `service_name` - ログに出力する送信先の名前
+/// * `retry_after` - 429 のレスポンスから再送までの待ち時間を取得する関数
+/// * `send` - リクエストを1回送信する関数
+pub async fn send_with_retry_after<S, F>(
+    service_name: &str,
+    retry_after: fn(&HttpStatusResponse) -> Option<Duration>,
+    mut send: S,
+) -> Result<HttpStatusResponse, NotificationError>
+where
+    S: FnMut() -> F,
+    F: Future<Output = Result<HttpStatusResponse, NotificationError>>,
+{
+    for _ in 0..=MAX_RATE_LIMIT_RETRIES {
+        let response = send().await?;
+
+        if response.status == 429 {
+            let wait = retry_after(&response).unwrap_or(Duration::from_secs(1));
+            if wait > MAX_RATE_LIMIT_WAIT {
+                return Err(NotificationError::SendError(format!(
+                    "Rate limited for {} seconds",
+                    wait.as_secs()
+                )));
+            }
+
+            warn!("{} is rate limited, retrying in {} ms", service_name, wait.as_millis());
+            rt::time::sleep(wait).await;
+            continue;
+        }
+        if !response.is_success() {
+            return Err(NotificationError::SendError(format!(
+                "Status: {}, Body: {}",
+                response.status, response.body
+            )));
+        }
+
+        return Ok(response);
+    }
+
+    Err(NotificationError::SendError(format!(
+        "Still rate limited after {} retries",
+        MAX_RATE_LIMIT_RETRIES
+    )))
+}
+
+/// 前回までに送信したメッセージを飛ばして、残りのメッセージを順番に送信する
+///
+/// 途中で失敗した場合は、それまでに送信が完了したメッセージ数をエラーに含める
+///
+/// # Arguments
+/// * `service_name` - ログに出力する送信先の名前
+/// * `messages` - 送信するメッセージのリスト
+/// * `sent_messages` - 前回までに送信が完了したメッセージ数
+/// * `send` - メッセージの順番とメッセージを受け取り、1件送信する関数
+pub async fn send_remaining<'a, M, S, F>(
+    service_name: &str,
+    messages: &'a [M],
+    sent_messages: usize,
+    mut send: S,
+) -> Result<(), NotificationError>
+where
+    S: FnMut(usize, &'a M) -> F,
+    F: Future<Output = Result<(), NotificationError>>,
+{
+    if sent_messages > 0 {
+        info!(
+            "Skipping {} of {} {} messages already sent",
+            sent_messages.min(messages.len()),
+            messages.len(),
+            service_name
+        );
+    }
+
+    for (index, message) in messages.iter().enumerate().skip(sent_messages) {
+        send(index, message).await.map_err(|e| {
+            let error = match e {
+                NotificationError::SendError(error) | NotificationError::PartiallySent { error, .. } => error,
+            };
+            NotificationError::PartiallySent {
+                sent_messages: index,
+                error,
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Webhookにメッセージを送信する
+///
+/// 429 Too Many Requests が返された場合は Retry-After ヘッダーの時間だけ待ってから再送する
+///
+/// # Arguments
+/// * `http_client` - HTTPクライアント
+/// * `webhook_url` - 送信先のWebhook URL
+/// * `body` - 送信するメッセージ
+/// * `service_name` - ログに出力する送信先の名前
+pub async fn post_with_retry_after<T, B>(
+    http_client: &T,
+    webhook_url: &str,
+    body: &B,
+    service_name: &str,
+) -> Result<(), NotificationError>
+where
+    T: HttpClient,
+    B: Serialize + ?Sized + Send + Sync,
+{
+    send_with_retry_after(service_name, retry_after_header, || async move {
+        http_client
+            .post_with_status(webhook_url, body)
+            .await
+            .map_err(|e| NotificationError::SendError(e.to_string()))
+    })
+    .await
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[actix_web::test]
+    async fn skips_sent_messages_and_reports_progress_on_failure() {
+        let sent = RefCell::new(Vec::new());
+        let messages = ["first", "second", "third", "fourth"];
+
+        let result = send_remaining("Test", &messages, 1, |index, message| {
+            let sent = &sent;
+            async move {
+                if index == 2 {
+                    return Err(NotificationError::SendError("Status: 500".to_string()));
+                }
+                sent.borrow_mut().push(*message);
+                Ok(())
+            }
+        })
+        .await;
+
+        assert_eq!(*sent.borrow(), vec!["second"]);
+        assert_eq!(result.unwrap_err().sent_messages(), 2);
+    }
+
+    #[actix_web::test]
+    async fn resumes_from_the_first_unsent_message() {
+        let sent = RefCell::new(Vec::new());
+        let messages = ["first", "second", "third"];
+
+        send_remaining("Test", &messages, 2, |_, message| {
+            sent.borrow_mut().push(*message);
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(*sent.borrow(), vec!["third"]);
+    }
+}
@@ -1,17 +1,26 @@
 use actix_web::{web, App, HttpServer};
 use dotenvy::dotenv;
-use log::{error, info};
+use log::{error, info, warn};
 use std::env;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::application::rss_summary_service::RssSummaryServiceImpl;
 use crate::application::use_case::fetch_and_summarize::FetchAndSummarizeUseCase;
-use crate::domain::rss_summary::RssSummaryService;
-use crate::infrastructure::discord::notification_service::DiscordNotificationService;
+use crate::domain::model::feed::FeedRegistry;
+use crate::domain::model::outbox::RetryPolicy;
+use crate::infrastructure::config::feed_config::{load_feed_registry, FeedConfigError};
 use crate::infrastructure::event::in_memory_event_publisher::{InMemoryEventPublisher, LoggingEventSubscriber};
-use crate::infrastructure::gemini::ai_service::GeminiAiService;
+use crate::infrastructure::ai_provider::AiProvider;
+use crate::infrastructure::cache::summary_cache::TtlSummaryCache;
 use crate::infrastructure::http_client::HttpClientImpl;
+use crate::infrastructure::notification_provider::NotificationProvider;
+use crate::infrastructure::repository::file_outbox_repository::FileOutboxRepository;
+use crate::infrastructure::repository::file_seen_item_repository::FileSeenItemRepository;
 use crate::infrastructure::repository::http_rss_repository::HttpRssRepository;
-use crate::presentation::http::handlers::handle_get_request;
+use crate::presentation::http::handlers::{handle_flush_outbox, handle_get_outbox, handle_get_request};
+use crate::presentation::scheduler::{start_outbox_retry, start_scheduler};
 
 mod application;
 mod domain;
@@ -29,49 +38,113 @@ async fn main() -> std::io::Result<()> {
         error!("Failed to load .env file: {}", e);
     }
 
+    // フィード設定の読み込み
+    let feed_config_path = env::var("FEEDS_CONFIG_PATH").unwrap_or_else(|_| "feeds.json".to_string());
+    let feed_registry = match load_feed_registry(&feed_config_path) {
+        Ok(registry) => registry,
+        Err(FeedConfigError::NotFound(path)) => {
+            warn!("Feed config {} not found, using default feed", path);
+            FeedRegistry::default()
+        }
+        Err(e) => {
+            error!("{}", e);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()));
+        }
+    };
+    info!("Loaded {} feeds", feed_registry.feeds().len());
+    let feed_registry = Arc::new(feed_registry);
+
     // 依存関係の設定
     let http_client = HttpClientImpl::new();
     
     // RSSリポジトリの初期化
     let rss_repository = HttpRssRepository::new(http_client.clone());
     
-    // Gemini APIのURLを取得
-    let gemini_url = env::var("GEMINI_API_URL").unwrap_or_else(|_| {
-        error!("GEMINI_API_URL is not set");
-        String::new()
-    });
-    
     // AIサービスの初期化
-    let _ai_service = GeminiAiService::new(http_client.clone(), gemini_url);
+    let ai_service = AiProvider::from_env(http_client.clone()).map_err(|e| {
+        error!("{}", e);
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+    })?;
     
     // RSSサマリーサービスの初期化
-    let summary_service = crate::application::rss_summary_service::RssSummaryServiceImpl::new(http_client.clone());
-    
-    // Discord通知サービスの初期化
-    let discord_url = env::var("DISCORD_WEBHOOK_URL").unwrap_or_else(|_| {
-        error!("DISCORD_WEBHOOK_URL is not set");
-        String::new()
-    });
+    let max_repair_attempts = env::var("SUMMARY_REPAIR_ATTEMPTS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(2);
+    let token_budget = env::var("SUMMARY_TOKEN_BUDGET")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30000);
+    let summary_cache_ttl = env::var("SUMMARY_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(86400);
+    let summary_cache = TtlSummaryCache::new(
+        Duration::from_secs(summary_cache_ttl),
+        env::var("SUMMARY_CACHE_PATH").ok().map(PathBuf::from),
+    );
+    let summary_service = RssSummaryServiceImpl::new(ai_service, summary_cache, max_repair_attempts, token_budget);
     
-    let notification_service = DiscordNotificationService::new(http_client.clone(), discord_url);
+    // 通知サービスの初期化
+    let notification_service = NotificationProvider::from_env(http_client.clone(), &feed_registry).map_err(|e| {
+        error!("{}", e);
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+    })?;
     
     // イベントパブリッシャーの初期化
     let event_publisher = InMemoryEventPublisher::new();
     event_publisher.add_subscriber(LoggingEventSubscriber);
     
+    // 通知済みアイテムストアの初期化
+    let seen_items_path = env::var("SEEN_ITEMS_PATH").unwrap_or_else(|_| "seen_items.json".to_string());
+    let seen_item_repository = FileSeenItemRepository::open(&seen_items_path).map_err(|e| {
+        error!("{}", e);
+        std::io::Error::other(e.to_string())
+    })?;
+
+    // アウトボックスの初期化
+    let outbox_path = env::var("OUTBOX_PATH").unwrap_or_else(|_| "outbox.json".to_string());
+    let outbox_repository = FileOutboxRepository::open(&outbox_path).map_err(|e| {
+        error!("{}", e);
+        std::io::Error::other(e.to_string())
+    })?;
+    let retry_base_secs = env::var("OUTBOX_RETRY_BASE_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(60);
+    let retry_max_secs = env::var("OUTBOX_RETRY_MAX_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(3600);
+    let retry_policy = RetryPolicy::new(Duration::from_secs(retry_base_secs), Duration::from_secs(retry_max_secs));
+    let outbox_poll_secs = env::var("OUTBOX_POLL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30);
+
     // ユースケースの初期化
     let use_case = Arc::new(FetchAndSummarizeUseCase::new(
         rss_repository,
         summary_service,
         notification_service,
         event_publisher,
+        seen_item_repository,
+        outbox_repository,
+        retry_policy,
     ));
 
+    // 定期実行の開始
+    start_scheduler(use_case.clone(), &feed_registry);
+    start_outbox_retry(use_case.clone(), Duration::from_secs(outbox_poll_secs));
+
     // サーバーの起動
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(use_case.clone()))
+            .app_data(web::Data::new(feed_registry.clone()))
             .service(handle_get_request)
+            .service(handle_get_outbox)
+            .service(handle_flush_outbox)
     })
     .bind("0.0.0.0:8080")?
     .run()
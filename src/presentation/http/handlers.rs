@@ -1,47 +1,123 @@
-use actix_web::{get, web, HttpResponse, Responder};
+use actix_web::{get, post, web, HttpResponse, Responder};
 use dotenvy::dotenv;
 use log::{error, info};
+use serde::Serialize;
 use std::sync::Arc;
 
-use crate::application::use_case::fetch_and_summarize::{AppError, FetchAndSummarizeUseCase};
-use crate::domain::event::rss_events::EventPublisher;
-use crate::domain::notification::NotificationService;
-use crate::domain::repository::rss_repository::RssRepository;
-use crate::domain::rss_summary::RssSummaryService;
+use crate::application::use_case::fetch_and_summarize::{
+    AppError, ExecutionOutcome, FeedExecutionResult, FetchAndSummarizeUseCase,
+};
+use crate::domain::model::feed::FeedRegistry;
 
 type UseCase = FetchAndSummarizeUseCase<
     crate::infrastructure::repository::http_rss_repository::HttpRssRepository<crate::infrastructure::http_client::HttpClientImpl>,
-    crate::application::rss_summary_service::RssSummaryServiceImpl<crate::infrastructure::http_client::HttpClientImpl>,
-    crate::infrastructure::discord::notification_service::DiscordNotificationService<crate::infrastructure::http_client::HttpClientImpl>,
-    crate::infrastructure::event::in_memory_event_publisher::InMemoryEventPublisher
+    crate::application::rss_summary_service::RssSummaryServiceImpl<
+        crate::infrastructure::ai_provider::AiProvider<crate::infrastructure::http_client::HttpClientImpl>,
+        crate::infrastructure::cache::summary_cache::TtlSummaryCache
+    >,
+    crate::infrastructure::notification_provider::NotificationProvider<crate::infrastructure::http_client::HttpClientImpl>,
+    crate::infrastructure::event::in_memory_event_publisher::InMemoryEventPublisher,
+    crate::infrastructure::repository::file_seen_item_repository::FileSeenItemRepository,
+    crate::infrastructure::repository::file_outbox_repository::FileOutboxRepository
 >;
 
-/// RSSフィードを取得し、要約してDiscordに送信するエンドポイント
+/// フィードごとの実行結果のレスポンス
+#[derive(Serialize, Debug)]
+struct FeedExecutionReport {
+    name: String,
+    url: String,
+    success: bool,
+    new_items: usize,
+    notification_queued: bool,
+    error: Option<String>,
+}
+
+impl From<&FeedExecutionResult> for FeedExecutionReport {
+    fn from(result: &FeedExecutionResult) -> Self {
+        Self {
+            name: result.feed.name.clone(),
+            url: result.feed.url.clone(),
+            success: result.is_success(),
+            new_items: result
+                .result
+                .as_ref()
+                .map(|outcome| outcome.new_item_count())
+                .unwrap_or(0),
+            notification_queued: matches!(
+                result.result,
+                Ok(ExecutionOutcome::NotificationQueued { .. })
+            ),
+            error: result.result.as_ref().err().map(error_message),
+        }
+    }
+}
+
+/// エラーをレスポンス用のメッセージに変換する
+///
+/// # Arguments
+/// * `error` - アプリケーションエラー
+fn error_message(error: &AppError) -> String {
+    match error {
+        AppError::Rss(_) => "Failed to fetch RSS feed".to_string(),
+        AppError::Summary(_) => "Failed to generate summary".to_string(),
+        AppError::Notification(_) => "Failed to send notification".to_string(),
+        AppError::SeenItemStore(_) => "Failed to access seen item store".to_string(),
+        AppError::Outbox(_) => "Failed to access outbox".to_string(),
+    }
+}
+
+/// 登録されている全てのRSSフィードを取得し、要約してDiscordに送信するエンドポイント
 #[get("/")]
 pub async fn handle_get_request(
     use_case: web::Data<Arc<UseCase>>,
+    feed_registry: web::Data<Arc<FeedRegistry>>,
 ) -> impl Responder {
     // 環境変数の読み込み
     if let Err(e) = dotenv() {
         error!("Failed to load .env file: {}", e);
     }
 
-    // RSSフィードのURL
-    let rss_feed_url = "https://zenn.dev/feed";
-
     // ユースケースの実行
-    match use_case.execute(rss_feed_url, 10).await {
-        Ok(_) => {
-            info!("Successfully processed RSS feed");
-            HttpResponse::NoContent().finish()
+    let results = use_case.execute_all(feed_registry.feeds()).await;
+    let reports: Vec<FeedExecutionReport> = results.iter().map(FeedExecutionReport::from).collect();
+
+    if results.iter().all(FeedExecutionResult::is_success) {
+        info!("Successfully processed {} RSS feeds", results.len());
+        HttpResponse::Ok().json(reports)
+    } else {
+        let failed = results.iter().filter(|r| !r.is_success()).count();
+        error!("Failed to process {} of {} RSS feeds", failed, results.len());
+        HttpResponse::InternalServerError().json(reports)
+    }
+}
+
+/// アウトボックスに保存されている通知を取得するエンドポイント
+#[get("/outbox")]
+pub async fn handle_get_outbox(use_case: web::Data<Arc<UseCase>>) -> impl Responder {
+    match use_case.outbox_entries() {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(e) => {
+            error!("Failed to read outbox: {}", e);
+            HttpResponse::InternalServerError().json(error_message(&e))
+        }
+    }
+}
+
+/// 再送時刻を待たずにアウトボックスの全ての通知を再送するエンドポイント
+#[post("/outbox/flush")]
+pub async fn handle_flush_outbox(use_case: web::Data<Arc<UseCase>>) -> impl Responder {
+    match use_case.retry_outbox(true).await {
+        Ok(report) if report.failed == 0 => {
+            info!("Flushed outbox: {:?}", report);
+            HttpResponse::Ok().json(report)
+        }
+        Ok(report) => {
+            error!("Failed to deliver {} outbox entries", report.failed);
+            HttpResponse::InternalServerError().json(report)
         }
         Err(e) => {
-            error!("Failed to process RSS feed: {}", e);
-            match e {
-                AppError::RssError(_) => HttpResponse::InternalServerError().body("Failed to fetch RSS feed"),
-                AppError::SummaryError(_) => HttpResponse::InternalServerError().body("Failed to generate summary"),
-                AppError::NotificationError(_) => HttpResponse::InternalServerError().body("Failed to send notification"),
-            }
+            error!("Failed to flush outbox: {}", e);
+            HttpResponse::InternalServerError().json(error_message(&e))
         }
     }
 }
@@ -0,0 +1,142 @@
+use actix_web::rt;
+use chrono::Local;
+use cron::Schedule;
+use log::{error, info, warn};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::use_case::fetch_and_summarize::FetchAndSummarizeUseCase;
+use crate::domain::event::rss_events::EventPublisher;
+use crate::domain::model::feed::{Feed, FeedRegistry, FeedSchedule};
+use crate::domain::notification::NotificationService;
+use crate::domain::repository::outbox_repository::OutboxRepository;
+use crate::domain::repository::rss_repository::RssRepository;
+use crate::domain::repository::seen_item_repository::SeenItemRepository;
+use crate::domain::rss_summary::RssSummaryService;
+
+/// スケジュールが設定されているフィードの定期実行を開始する
+///
+/// フィードごとにタスクを起動し、前回の実行が終わってから次の実行時刻を計算するため、
+/// 同じフィードの実行が重なることはない
+///
+/// # Arguments
+/// * `use_case` - 実行するユースケース
+/// * `feed_registry` - フィードレジストリ
+pub fn start_scheduler<R, S, N, E, K, O>(
+    use_case: Arc<FetchAndSummarizeUseCase<R, S, N, E, K, O>>,
+    feed_registry: &FeedRegistry,
+) where
+    R: RssRepository + 'static,
+    S: RssSummaryService + 'static,
+    N: NotificationService + 'static,
+    E: EventPublisher + 'static,
+    K: SeenItemRepository + 'static,
+    O: OutboxRepository + 'static,
+{
+    for feed in feed_registry.feeds() {
+        let Some(schedule) = feed.schedule.clone() else {
+            continue;
+        };
+
+        info!("Scheduling feed '{}'", feed.name);
+        rt::spawn(run_schedule(use_case.clone(), feed.clone(), schedule));
+    }
+}
+
+/// フィードをスケジュールに従って繰り返し実行する
+///
+/// # Arguments
+/// * `use_case` - 実行するユースケース
+/// * `feed` - 処理対象のフィード
+/// * `schedule` - フィードのスケジュール
+async fn run_schedule<R, S, N, E, K, O>(
+    use_case: Arc<FetchAndSummarizeUseCase<R, S, N, E, K, O>>,
+    feed: Feed,
+    schedule: FeedSchedule,
+) where
+    R: RssRepository,
+    S: RssSummaryService,
+    N: NotificationService,
+    E: EventPublisher,
+    K: SeenItemRepository,
+    O: OutboxRepository,
+{
+    loop {
+        let Some(delay) = next_delay(&schedule) else {
+            error!("Failed to calculate next run of feed '{}', stopping its schedule", feed.name);
+            return;
+        };
+        let delay = delay + jitter(schedule.jitter_secs);
+        info!("Next run of feed '{}' in {} seconds", feed.name, delay.as_secs());
+        rt::time::sleep(delay).await;
+
+        match use_case.execute_feed(&feed).await {
+            Ok(outcome) => info!("Scheduled run of feed '{}' finished: {:?}", feed.name, outcome),
+            Err(e) => warn!("Scheduled run of feed '{}' failed: {}", feed.name, e),
+        }
+    }
+}
+
+/// アウトボックスに保存された通知の定期的な再送を開始する
+///
+/// 各エントリーは再送時刻になったものだけが送信される
+///
+/// # Arguments
+/// * `use_case` - 実行するユースケース
+/// * `poll_interval` - アウトボックスを確認する間隔
+pub fn start_outbox_retry<R, S, N, E, K, O>(
+    use_case: Arc<FetchAndSummarizeUseCase<R, S, N, E, K, O>>,
+    poll_interval: Duration,
+) where
+    R: RssRepository + 'static,
+    S: RssSummaryService + 'static,
+    N: NotificationService + 'static,
+    E: EventPublisher + 'static,
+    K: SeenItemRepository + 'static,
+    O: OutboxRepository + 'static,
+{
+    rt::spawn(async move {
+        loop {
+            rt::time::sleep(poll_interval).await;
+
+            match use_case.retry_outbox(false).await {
+                Ok(report) if report.delivered > 0 || report.failed > 0 => {
+                    info!("Outbox retry finished: {:?}", report)
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Outbox retry failed: {}", e),
+            }
+        }
+    });
+}
+
+/// 次の実行までの待ち時間を計算する
+///
+/// # Arguments
+/// * `schedule` - フィードのスケジュール
+fn next_delay(schedule: &FeedSchedule) -> Option<Duration> {
+    if let Some(interval_secs) = schedule.interval_secs {
+        return Some(Duration::from_secs(interval_secs));
+    }
+
+    let cron = Schedule::from_str(schedule.cron.as_deref()?).ok()?;
+    let next = cron.upcoming(Local).next()?;
+    (next - Local::now()).to_std().ok()
+}
+
+/// 0秒から指定した秒数までのランダムな待ち時間を生成する
+///
+/// # Arguments
+/// * `max_secs` - 最大秒数
+fn jitter(max_secs: u64) -> Duration {
+    if max_secs == 0 {
+        return Duration::ZERO;
+    }
+
+    // 乱数のためだけに依存を増やさないよう、ランダムなシードを持つハッシャーを使用する
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(random % (max_secs * 1000 + 1))
+}